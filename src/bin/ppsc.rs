@@ -0,0 +1,249 @@
+//! Generates Rust types with SCALE codecs from `.proto` files, outside of a build script.
+//!
+//! Every option of `ppsc_build::Config::set_option` is a flag, with dashes or underscores:
+//!
+//! ```sh
+//! ppsc -I protos -o src/generated --btree-map . --extern-path .common=::common items.proto
+//! ```
+//!
+//! With `--check`, nothing is written and the command fails when the files in the output
+//! directory differ from a fresh generation, listing them.
+//!
+//! With `--config`, the options are loaded from a configuration file first, as described in
+//! `ppsc_build::Config::from_file`, and the flags add to them:
+//!
+//! ```sh
+//! ppsc --config ppsc.toml --check
+//! ```
+
+use std::env;
+use std::process::ExitCode;
+
+use ppsc_build::{BOOLEAN_OPTIONS, Config};
+
+const USAGE: &str = "\
+Usage: ppsc [OPTIONS] -o <DIR> <PROTO>...
+       ppsc [OPTIONS] --config <FILE> [PROTO]...
+
+Options:
+  -I, --include <DIR>       Include directory to search for imports, the current one by default
+  -o, --out-dir <DIR>       Directory to write the generated files to
+      --include-file <FILE> Also write an include file declaring the modules of the generated files
+  -c, --config <FILE>       Load the options, `.proto` files and directories from a TOML file
+      --check               Fail if the output directory is not up to date, instead of writing to it
+  -h, --help                Print this help
+      --<KEY> <VALUE>       Set any option of `Config::set_option`, such as `--btree-map .` or
+                            `--extern-path .common=::common`; boolean options such as
+                            `--type-info` take no value, unless given as `--type-info=false`
+";
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    protos: Vec<String>,
+    includes: Vec<String>,
+    out_dir: Option<String>,
+    config: Option<String>,
+    check: bool,
+    help: bool,
+    options: Vec<(String, String)>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                parsed.protos.extend(args.by_ref());
+                break;
+            }
+            if !arg.starts_with('-') {
+                parsed.protos.push(arg);
+                continue;
+            }
+
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None if arg.starts_with("-I") && arg.len() > 2 => ("-I", Some(arg[2..].to_owned())),
+                None => (arg.as_str(), None),
+            };
+            let key = match name {
+                "-h" | "--help" => "help".to_owned(),
+                "-I" | "--include" => "include".to_owned(),
+                "-o" => "out_dir".to_owned(),
+                "-c" => "config".to_owned(),
+                _ => match name.strip_prefix("--") {
+                    Some(key) => key.replace('-', "_"),
+                    None => return Err(format!("unknown flag `{}`", name)),
+                },
+            };
+
+            match key.as_str() {
+                "help" | "check" if inline_value.is_some() => {
+                    return Err(format!("flag `{}` takes no value", name));
+                }
+                "help" => parsed.help = true,
+                "check" => parsed.check = true,
+                _ => {
+                    // Boolean options are set without a value, unless it is given inline.
+                    let value = match inline_value {
+                        Some(value) => value,
+                        None if BOOLEAN_OPTIONS.contains(&key.as_str()) => String::new(),
+                        None => args
+                            .next()
+                            .ok_or_else(|| format!("flag `{}` expects a value", name))?,
+                    };
+                    match key.as_str() {
+                        "include" => parsed.includes.push(value),
+                        "out_dir" => parsed.out_dir = Some(value),
+                        "config" => parsed.config = Some(value),
+                        _ => parsed.options.push((key, value)),
+                    }
+                }
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) if args.help => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(args) => args,
+        Err(message) => return usage_error(&message),
+    };
+    if args.config.is_none() {
+        if args.out_dir.is_none() {
+            return usage_error("missing the output directory `--out-dir`");
+        }
+        if args.protos.is_empty() {
+            return usage_error("missing the `.proto` files to generate code for");
+        }
+    }
+
+    let config = match &args.config {
+        Some(file) => Config::from_file(file),
+        None => Ok(Config::new()),
+    };
+    let config = config.and_then(|mut config| {
+        config.emit_rerun_if_changed(false);
+        if let Some(out_dir) = &args.out_dir {
+            config.out_dir(out_dir);
+        }
+        let protos = args.protos.iter().map(|proto| ("protos", proto));
+        let includes = args.includes.iter().map(|include| ("includes", include));
+        let options = args
+            .options
+            .iter()
+            .map(|(key, value)| (key.as_str(), value));
+        for (key, value) in protos.chain(includes).chain(options) {
+            config.set_option(key, value)?;
+        }
+        Ok(config)
+    });
+    let mut config = match config {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = if args.check {
+        config.check().map(|stale| {
+            for path in &stale {
+                eprintln!("out of date: {}", path.display());
+            }
+            stale.is_empty()
+        })
+    } else {
+        config.compile().map(|()| true)
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(&[
+            "-Iprotos",
+            "--include",
+            "vendor",
+            "-o",
+            "src/generated",
+            "--btree-map",
+            ".",
+            "--type-info",
+            "--extern-crate-alloc",
+            "--emit-rerun-if-changed=false",
+            "--extern_path=.common=::common",
+            "--check",
+            "-c",
+            "ppsc.toml",
+            "items.proto",
+            "--",
+            "-odd.proto",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            args,
+            Args {
+                protos: vec![String::from("items.proto"), String::from("-odd.proto")],
+                includes: vec![String::from("protos"), String::from("vendor")],
+                out_dir: Some(String::from("src/generated")),
+                config: Some(String::from("ppsc.toml")),
+                check: true,
+                help: false,
+                options: vec![
+                    (String::from("btree_map"), String::from(".")),
+                    (String::from("type_info"), String::new()),
+                    (String::from("extern_crate_alloc"), String::new()),
+                    (String::from("emit_rerun_if_changed"), String::from("false")),
+                    (
+                        String::from("extern_path"),
+                        String::from(".common=::common")
+                    ),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(&["-x"]).unwrap_err(), "unknown flag `-x`".to_owned());
+        assert_eq!(
+            parse(&["items.proto", "--boxed"]).unwrap_err(),
+            "flag `--boxed` expects a value".to_owned()
+        );
+        assert_eq!(
+            parse(&["--check=false"]).unwrap_err(),
+            "flag `--check` takes no value".to_owned()
+        );
+        assert_eq!(
+            parse(&["--help=yes"]).unwrap_err(),
+            "flag `--help` takes no value".to_owned()
+        );
+    }
+}
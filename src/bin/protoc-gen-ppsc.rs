@@ -0,0 +1,19 @@
+//! A `protoc` plugin generating Rust types with SCALE codecs.
+//!
+//! Options are passed with `--ppsc_opt`, as described in `ppsc_build::Config::plugin_response`:
+//!
+//! ```sh
+//! protoc --ppsc_out=src/generated --ppsc_opt=compact=.,type_info items.proto
+//! ```
+
+use std::io::{self, Read, Write};
+
+use ppsc_build::Config;
+
+fn main() -> io::Result<()> {
+    let mut request = Vec::new();
+    io::stdin().read_to_end(&mut request)?;
+
+    let response = Config::new().plugin_response(&request);
+    io::stdout().write_all(&response)
+}
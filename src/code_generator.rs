@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::io::Result;
 use std::iter;
 
 use itertools::{Either, Itertools};
@@ -11,23 +12,30 @@ use prost_types::{
     FileDescriptorProto, OneofDescriptorProto, ServiceDescriptorProto, SourceCodeInfo,
 };
 
-use crate::Config;
 use crate::ast::{Comments, Method, Service};
 use crate::context::Context;
+use crate::derives::{Derives, attribute_derives_default, is_codec_derive};
+use crate::error::Span;
+use crate::features::EnumType;
 use crate::ident::{strip_enum_prefix, to_snake, to_upper_camel};
+use crate::{
+    BytesType, Config, EncodingProfile, EnumIndex, FieldOrder, FloatStrategy, MapType, NoStdMode,
+    OneofIndex, SignedVarint,
+};
 
 mod c_escaping;
+use c_escaping::unescape_c_escape_string;
 
-mod syntax;
-use syntax::Syntax;
+mod support;
+pub(crate) use support::SupportType;
 
 /// State object for the code generation process on a single input file.
 pub struct CodeGenerator<'a, 'b> {
     context: &'a mut Context<'b>,
+    file_name: String,
     package: String,
     type_path: Vec<String>,
     source_info: Option<SourceCodeInfo>,
-    syntax: Syntax,
     depth: u8,
     path: Vec<i32>,
     buf: &'a mut String,
@@ -75,6 +83,114 @@ impl OneofField {
     fn rust_name(&self) -> String {
         to_snake(self.descriptor.name())
     }
+
+    /// The declaration position of the first member of the oneof.
+    fn first_path_index(&self) -> i32 {
+        self.fields
+            .iter()
+            .map(|field| field.path_index)
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// The lowest field number among the members of the oneof.
+    fn first_number(&self) -> i32 {
+        self.fields
+            .iter()
+            .map(|field| field.descriptor.number())
+            .min()
+            .unwrap_or_default()
+    }
+}
+
+/// How an integer field is represented in SCALE.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum IntegerEncoding {
+    Fixed,
+    Compact,
+    ZigZag(SupportType),
+}
+
+/// The value a message struct member is initialised with by the generated `Default`
+/// implementation.
+struct MemberDefault {
+    rust_name: String,
+    value: String,
+    accessor: Option<Accessor>,
+}
+
+impl MemberDefault {
+    /// Returns whether the member is initialised with the `Default` of its type, so that
+    /// `Default` can be derived.
+    fn is_derivable(&self) -> bool {
+        self.value == "Default::default()" || self.value == "None"
+    }
+}
+
+/// The default of a singular field that is not a message.
+struct ScalarDefault {
+    value: String,
+    accessor: Option<Accessor>,
+}
+
+/// A method returning the value of an optional field, or its default value if unset.
+struct Accessor {
+    ty: String,
+    /// The returned expression when the field is set, in terms of a `value` reference.
+    some: String,
+    /// The returned expression when the field is unset.
+    none: String,
+}
+
+impl Accessor {
+    fn new(ty: impl Into<String>, some: &str, none: impl Into<String>) -> Self {
+        Self {
+            ty: ty.into(),
+            some: some.to_owned(),
+            none: none.into(),
+        }
+    }
+}
+
+/// A member of a generated message struct: a regular field or a oneof.
+enum MessageMember<'a> {
+    Field(&'a Field),
+    Oneof(&'a OneofField),
+}
+
+impl MessageMember<'_> {
+    fn path_index(&self) -> i32 {
+        match self {
+            MessageMember::Field(field) => field.path_index,
+            MessageMember::Oneof(oneof) => oneof.first_path_index(),
+        }
+    }
+
+    fn number(&self) -> i32 {
+        match self {
+            MessageMember::Field(field) => field.descriptor.number(),
+            MessageMember::Oneof(oneof) => oneof.first_number(),
+        }
+    }
+}
+
+/// Orders the members of a message struct according to `order`.
+fn order_members<'a>(
+    fields: &'a [Field],
+    oneofs: &'a [OneofField],
+    order: FieldOrder,
+) -> Vec<MessageMember<'a>> {
+    let mut members: Vec<MessageMember<'a>> = fields
+        .iter()
+        .map(MessageMember::Field)
+        .chain(oneofs.iter().map(MessageMember::Oneof))
+        .collect();
+    match order {
+        FieldOrder::Legacy => {}
+        FieldOrder::Declaration => members.sort_by_key(MessageMember::path_index),
+        FieldOrder::FieldNumber => members.sort_by_key(MessageMember::number),
+    }
+    members
 }
 
 impl<'b> CodeGenerator<'_, 'b> {
@@ -82,7 +198,11 @@ impl<'b> CodeGenerator<'_, 'b> {
         self.context.config()
     }
 
-    pub(crate) fn generate(context: &mut Context<'b>, file: FileDescriptorProto, buf: &mut String) {
+    pub(crate) fn generate(
+        context: &mut Context<'b>,
+        file: FileDescriptorProto,
+        buf: &mut String,
+    ) -> Result<()> {
         let source_info = file.source_code_info.map(|mut s| {
             s.location.retain(|loc| {
                 let len = loc.path.len();
@@ -94,10 +214,10 @@ impl<'b> CodeGenerator<'_, 'b> {
 
         let mut code_gen = CodeGenerator {
             context,
+            file_name: file.name.unwrap_or_default(),
             package: file.package.unwrap_or_default(),
             type_path: Vec::new(),
             source_info,
-            syntax: file.syntax.as_deref().into(),
             depth: 0,
             path: Vec::new(),
             buf,
@@ -105,19 +225,34 @@ impl<'b> CodeGenerator<'_, 'b> {
 
         debug!(
             "file: {:?}, package: {:?}",
-            file.name.as_ref().unwrap(),
-            code_gen.package
+            code_gen.file_name, code_gen.package
         );
-        code_gen.buf.push_str("extern crate alloc;\n");
+        // With an include file, the include file declares `alloc` once for all modules.
+        if code_gen.config().no_std_mode != NoStdMode::Std
+            && code_gen.config().extern_crate_alloc
+            && code_gen.config().include_file.is_none()
+        {
+            code_gen.buf.push_str("extern crate alloc;\n");
+        }
         code_gen.push_indent();
-        code_gen
-            .buf
-            .push_str("use parity_scale_codec::{Encode, Decode};\n\n");
+        let imports = format!(
+            "use {}::{{Encode, Decode}};\n",
+            code_gen.config().codec_crate_path()
+        );
+        code_gen.buf.push_str(&imports);
+        if code_gen.config().type_info {
+            let imports = format!(
+                "use {}::TypeInfo;\n",
+                code_gen.config().scale_info_crate_path()
+            );
+            code_gen.buf.push_str(&imports);
+        }
+        code_gen.buf.push('\n');
 
         code_gen.path.push(4);
         for (idx, message) in file.message_type.into_iter().enumerate() {
             code_gen.path.push(idx as i32);
-            code_gen.append_message(message);
+            code_gen.append_message(message)?;
             code_gen.path.pop();
         }
         code_gen.path.pop();
@@ -125,7 +260,7 @@ impl<'b> CodeGenerator<'_, 'b> {
         code_gen.path.push(5);
         for (idx, desc) in file.enum_type.into_iter().enumerate() {
             code_gen.path.push(idx as i32);
-            code_gen.append_enum(desc);
+            code_gen.append_enum(desc)?;
             code_gen.path.pop();
         }
         code_gen.path.pop();
@@ -144,9 +279,11 @@ impl<'b> CodeGenerator<'_, 'b> {
 
             code_gen.path.pop();
         }
+
+        Ok(())
     }
 
-    fn append_message(&mut self, message: DescriptorProto) {
+    fn append_message(&mut self, message: DescriptorProto) -> Result<()> {
         debug!("  message: {:?}", message.name());
 
         let message_name = message.name().to_string();
@@ -158,9 +295,15 @@ impl<'b> CodeGenerator<'_, 'b> {
             .resolve_extern_ident(&fq_message_name)
             .is_some()
         {
-            return;
+            return Ok(());
         }
 
+        self.validate_message(&fq_message_name, &message)?;
+
+        let mut derives = self
+            .context
+            .derives(&fq_message_name, &fq_message_name, &message.field);
+
         // Split the nested message types into a vector of normal nested message types, and a map
         // of the map field entry types. The path index of the nested message types is preserved so
         // that comments can be retrieved.
@@ -179,9 +322,6 @@ impl<'b> CodeGenerator<'_, 'b> {
                 {
                     let key = nested_type.field[0].clone();
                     let value = nested_type.field[1].clone();
-                    assert_eq!("key", key.name());
-                    assert_eq!("value", value.name());
-
                     let name = format!("{}.{}", &fq_message_name, nested_type.name());
                     Either::Right((name, (key, value)))
                 } else {
@@ -222,43 +362,85 @@ impl<'b> CodeGenerator<'_, 'b> {
         self.append_doc(&fq_message_name, None);
         self.append_type_attributes(&fq_message_name);
         self.append_message_attributes(&fq_message_name);
-        self.push_indent();
-        self.buf.push_str(&format!("#[derive(Encode, Decode)]\n"));
-        // self.append_skip_debug(&fq_message_name);
+        let redact_debug = derives.derives.iter().any(|derive| derive == "Debug")
+            && fields
+                .iter()
+                .map(|field| field.descriptor.name())
+                .chain(oneof_fields.iter().map(|oneof| oneof.descriptor.name()))
+                .any(|name| self.context.should_redact_debug(&fq_message_name, name));
+        if redact_debug {
+            derives.derives.retain(|derive| derive != "Debug");
+        }
+        let derives_start = self.buf.len();
+        self.append_derives(&derives);
+        let struct_start = self.buf.len();
         self.push_indent();
         self.buf.push_str("pub struct ");
         self.buf.push_str(&to_upper_camel(&message_name));
         self.buf.push_str(" {\n");
 
         self.depth += 1;
-        self.path.push(2);
-        for field in &fields {
-            self.path.push(field.path_index);
-            match field
-                .descriptor
-                .type_name
-                .as_ref()
-                .and_then(|type_name| map_types.get(type_name))
-            {
-                Some((key, value)) => self.append_map_field(&fq_message_name, field, key, value),
-                None => self.append_field(&fq_message_name, field),
+        let mut defaults = Vec::with_capacity(fields.len() + oneof_fields.len());
+        for member in order_members(&fields, &oneof_fields, self.config().field_order) {
+            match member {
+                MessageMember::Field(field) => {
+                    self.path.push(2);
+                    self.path.push(field.path_index);
+                    let default = match field
+                        .descriptor
+                        .type_name
+                        .as_ref()
+                        .and_then(|type_name| map_types.get(type_name))
+                    {
+                        Some((key, value)) => {
+                            self.append_map_field(&fq_message_name, field, key, value)?
+                        }
+                        None => self.append_field(&fq_message_name, field)?,
+                    };
+                    defaults.push(default);
+                    self.path.pop();
+                    self.path.pop();
+                }
+                MessageMember::Oneof(oneof) => {
+                    self.path.push(8);
+                    self.path.push(oneof.path_index);
+                    self.append_oneof_field(&message_name, &fq_message_name, oneof);
+                    defaults.push(MemberDefault {
+                        rust_name: oneof.rust_name(),
+                        value: String::from("None"),
+                        accessor: None,
+                    });
+                    self.path.pop();
+                    self.path.pop();
+                }
             }
-            self.path.pop();
-        }
-        self.path.pop();
-
-        self.path.push(8);
-        for oneof in &oneof_fields {
-            self.path.push(oneof.path_index);
-            self.append_oneof_field(&message_name, &fq_message_name, oneof);
-            self.path.pop();
         }
-        self.path.pop();
 
         self.depth -= 1;
         self.push_indent();
         self.buf.push_str("}\n");
 
+        let attribute_derived = self
+            .context
+            .type_attributes(&fq_message_name)
+            .chain(self.context.message_attributes(&fq_message_name))
+            .any(attribute_derives_default);
+        let derive_default = !attribute_derived && defaults.iter().all(MemberDefault::is_derivable);
+        if derive_default {
+            // The member defaults are only known once the struct is written, so its derives are
+            // written again with `Default`.
+            let definition = self.buf.split_off(struct_start);
+            self.buf.truncate(derives_start);
+            derives.derives.push(String::from("Default"));
+            self.append_derives(&derives);
+            self.buf.push_str(&definition);
+        }
+
+        if redact_debug {
+            self.append_message_debug(&message_name, &fq_message_name, &fields, &oneof_fields);
+        }
+        self.append_message_default(&message_name, defaults, attribute_derived || derive_default);
+
         if !message.enum_type.is_empty() || !nested_types.is_empty() || !oneof_fields.is_empty() {
             self.push_mod(&message_name);
             self.path.push(3);
@@ -266,7 +448,7 @@ impl<'b> CodeGenerator<'_, 'b> {
             self.buf.push_str("use super::*;\n\n");
             for (nested_type, idx) in nested_types {
                 self.path.push(idx as i32);
-                self.append_message(nested_type);
+                self.append_message(nested_type)?;
                 self.path.pop();
             }
             self.path.pop();
@@ -274,17 +456,62 @@ impl<'b> CodeGenerator<'_, 'b> {
             self.path.push(4);
             for (idx, nested_enum) in message.enum_type.into_iter().enumerate() {
                 self.path.push(idx as i32);
-                self.append_enum(nested_enum);
+                self.append_enum(nested_enum)?;
                 self.path.pop();
             }
             self.path.pop();
 
             for oneof in &oneof_fields {
-                self.append_oneof(&fq_message_name, oneof);
+                self.append_oneof(&fq_message_name, oneof)?;
             }
 
             self.pop_mod();
         }
+
+        Ok(())
+    }
+
+    /// Checks the parts of a message descriptor that code generation relies on: that map entries
+    /// have a `key` and a `value` field, and that type names are fully qualified.
+    fn validate_message(&self, fq_message_name: &str, message: &DescriptorProto) -> Result<()> {
+        for (idx, nested_type) in message.nested_type.iter().enumerate() {
+            let map_entry = nested_type
+                .options
+                .as_ref()
+                .and_then(|options| options.map_entry)
+                .unwrap_or(false);
+            let fields: Vec<&str> = nested_type.field.iter().map(|field| field.name()).collect();
+            if map_entry && fields != ["key", "value"] {
+                let fq_entry_name = format!("{}.{}", fq_message_name, nested_type.name());
+                return Err(crate::Error::InvalidSyntax {
+                    location: self.error_location(&[3, idx as i32], fq_entry_name.clone()),
+                    message: format!(
+                        "map entry `{}` must have a `key` and a `value` field",
+                        fq_entry_name
+                    ),
+                }
+                .into());
+            }
+        }
+
+        for (idx, field) in message.field.iter().enumerate() {
+            if matches!(field.r#type(), Type::Message | Type::Enum | Type::Group)
+                && !field.type_name().starts_with('.')
+            {
+                let fq_field_name = format!("{}.{}", fq_message_name, field.name());
+                return Err(crate::Error::InvalidSyntax {
+                    location: self.error_location(&[2, idx as i32], fq_field_name.clone()),
+                    message: format!(
+                        "field `{}`: type name `{}` is not fully qualified",
+                        fq_field_name,
+                        field.type_name()
+                    ),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
     }
 
     fn append_type_attributes(&mut self, fq_message_name: &str) {
@@ -314,6 +541,232 @@ impl<'b> CodeGenerator<'_, 'b> {
         }
     }
 
+    fn append_derives(&mut self, derives: &Derives) {
+        if !derives.derives.is_empty() {
+            self.push_indent();
+            self.buf
+                .push_str(&format!("#[derive({})]\n", derives.derives.join(", ")));
+        }
+        if let (false, NoStdMode::Feature(feature)) =
+            (derives.std_derives.is_empty(), &self.config().no_std_mode)
+        {
+            let attribute = format!(
+                "#[cfg_attr(feature = \"{}\", derive({}))]\n",
+                feature,
+                derives.std_derives.join(", ")
+            );
+            self.push_indent();
+            self.buf.push_str(&attribute);
+        }
+        let codec_derive = derives
+            .derives
+            .iter()
+            .chain(&derives.std_derives)
+            .any(|derive| is_codec_derive(derive));
+        if let (true, Some(codec_crate)) = (codec_derive, &self.config().codec_crate) {
+            let attribute = format!("#[codec(crate = {})]\n", codec_crate);
+            self.push_indent();
+            self.buf.push_str(&attribute);
+        }
+        if self.config().type_info {
+            self.push_indent();
+            self.buf
+                .push_str("#[scale_info(capture_docs = \"always\")]\n");
+            if let Some(scale_info_crate) = &self.config().scale_info_crate {
+                let attribute = format!("#[scale_info(crate = {})]\n", scale_info_crate);
+                self.push_indent();
+                self.buf.push_str(&attribute);
+            }
+        }
+    }
+
+    /// Appends a manual `Debug` implementation for a message, printing `<redacted>` for the
+    /// fields and oneofs matched by `Config::skip_debug`.
+    fn append_message_debug(
+        &mut self,
+        message_name: &str,
+        fq_message_name: &str,
+        fields: &[Field],
+        oneof_fields: &[OneofField],
+    ) {
+        let struct_name = to_upper_camel(message_name);
+        self.push_indent();
+        self.buf
+            .push_str(&format!("impl core::fmt::Debug for {} {{\n", struct_name));
+        self.depth += 1;
+        self.push_indent();
+        self.buf
+            .push_str("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+        self.depth += 1;
+        self.push_indent();
+        self.buf
+            .push_str(&format!("f.debug_struct(\"{}\")\n", struct_name));
+        self.depth += 1;
+        for member in order_members(fields, oneof_fields, self.config().field_order) {
+            let (proto_name, rust_name) = match member {
+                MessageMember::Field(field) => (field.descriptor.name(), field.rust_name()),
+                MessageMember::Oneof(oneof) => (oneof.descriptor.name(), oneof.rust_name()),
+            };
+            let value = if self
+                .context
+                .should_redact_debug(fq_message_name, proto_name)
+            {
+                String::from("&format_args!(\"<redacted>\")")
+            } else {
+                format!("&self.{}", rust_name)
+            };
+            self.push_indent();
+            self.buf.push_str(&format!(
+                ".field(\"{}\", {})\n",
+                rust_name.trim_start_matches("r#"),
+                value
+            ));
+        }
+        self.push_indent();
+        self.buf.push_str(".finish()\n");
+
+        self.depth -= 2;
+        self.push_indent();
+        self.buf.push_str("}\n"); // End of fmt()
+
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n"); // End of impl
+    }
+
+    /// Appends the `Default` implementation of a message, followed by the accessors of its
+    /// optional fields.
+    ///
+    /// The implementation is left out when `Default` is `derived`, by the generated derives or
+    /// the type attributes of the message.
+    fn append_message_default(
+        &mut self,
+        message_name: &str,
+        defaults: Vec<MemberDefault>,
+        derived: bool,
+    ) {
+        let struct_name = to_upper_camel(message_name);
+        if !derived {
+            self.push_indent();
+            self.buf
+                .push_str(&format!("impl Default for {} {{\n", struct_name));
+            self.depth += 1;
+            self.push_indent();
+            self.buf.push_str("fn default() -> Self {\n");
+            self.depth += 1;
+            // A message without members always derives `Default`.
+            self.push_indent();
+            self.buf.push_str("Self {\n");
+            self.depth += 1;
+            for default in &defaults {
+                self.push_indent();
+                self.buf
+                    .push_str(&format!("{}: {},\n", default.rust_name, default.value));
+            }
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n"); // End of default()
+
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n"); // End of impl
+        }
+
+        let accessors: Vec<(String, Accessor)> = defaults
+            .into_iter()
+            .filter_map(|default| Some((default.rust_name, default.accessor?)))
+            .collect();
+        if accessors.is_empty() {
+            return;
+        }
+        self.push_indent();
+        self.buf.push_str(&format!("impl {} {{\n", struct_name));
+        self.depth += 1;
+        for (rust_name, accessor) in accessors {
+            let name = rust_name.trim_start_matches("r#");
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "/// Returns the value of `{}`, or the default value if `{}` is unset.\n",
+                name, name
+            ));
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "pub fn {}(&self) -> {} {{\n",
+                rust_name, accessor.ty
+            ));
+            self.depth += 1;
+            self.push_indent();
+            self.buf
+                .push_str(&format!("match &self.{} {{\n", rust_name));
+            self.depth += 1;
+            self.push_indent();
+            self.buf
+                .push_str(&format!("Some(value) => {},\n", accessor.some));
+            self.push_indent();
+            self.buf.push_str(&format!("None => {},\n", accessor.none));
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n"); // End of match
+
+            self.depth -= 1;
+            self.push_indent();
+            self.buf.push_str("}\n");
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n"); // End of impl
+    }
+
+    /// Appends a manual `Debug` implementation for a oneof, printing `<redacted>` in place of the
+    /// values of the fields matched by `Config::skip_debug`.
+    fn append_oneof_debug(&mut self, fq_message_name: &str, oneof: &OneofField) {
+        let enum_name = to_upper_camel(oneof.descriptor.name());
+        self.push_indent();
+        self.buf
+            .push_str(&format!("impl core::fmt::Debug for {} {{\n", enum_name));
+        self.depth += 1;
+        self.push_indent();
+        self.buf
+            .push_str("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {\n");
+        self.depth += 1;
+        self.push_indent();
+        self.buf.push_str("match self {\n");
+        self.depth += 1;
+        for field in &oneof.fields {
+            let variant = to_upper_camel(field.descriptor.name());
+            self.push_indent();
+            if self
+                .context
+                .should_redact_debug(fq_message_name, field.descriptor.name())
+            {
+                self.buf.push_str(&format!(
+                    "Self::{}(_) => f.debug_tuple(\"{}\").field(&format_args!(\"<redacted>\")).finish(),\n",
+                    variant, variant
+                ));
+            } else {
+                self.buf.push_str(&format!(
+                    "Self::{}(value) => f.debug_tuple(\"{}\").field(value).finish(),\n",
+                    variant, variant
+                ));
+            }
+        }
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n"); // End of match
+
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n"); // End of fmt()
+
+        self.depth -= 1;
+        self.push_indent();
+        self.buf.push_str("}\n"); // End of impl
+    }
+
     fn append_field_attributes(&mut self, fq_message_name: &str, field_name: &str) {
         assert_eq!(b'.', fq_message_name.as_bytes()[0]);
         for attribute in self.context.field_attributes(fq_message_name, field_name) {
@@ -323,13 +776,29 @@ impl<'b> CodeGenerator<'_, 'b> {
         }
     }
 
-    fn append_field(&mut self, fq_message_name: &str, field: &Field) {
+    fn append_field(&mut self, fq_message_name: &str, field: &Field) -> Result<MemberDefault> {
         let repeated = field.descriptor.label() == Label::Repeated;
-        let optional = self.optional(&field.descriptor);
+        let optional = self.context.is_optional(fq_message_name, &field.descriptor);
         let boxed = self
             .context
             .should_box_message_field(fq_message_name, &field.descriptor);
-        let ty = self.resolve_type(&field.descriptor, fq_message_name);
+        let (mut ty, encoding) =
+            self.resolve_encoded_type(&field.descriptor, fq_message_name, field.descriptor.name())?;
+        let compact = encoding == IntegerEncoding::Compact;
+        if compact && (repeated || optional) {
+            ty = compact_type(self.config().codec_crate_path(), &ty);
+        }
+        let max_len = self
+            .context
+            .max_len(fq_message_name, field.descriptor.name());
+        let fixed_bytes =
+            self.context
+                .fixed_bytes(fq_message_name, field.descriptor.name(), &field.descriptor);
+        if let (false, Some(max_len), Type::String | Type::Bytes, None) =
+            (repeated, max_len, field.descriptor.r#type(), fixed_bytes)
+        {
+            ty = bounded_vec_type("u8", max_len);
+        }
 
         debug!(
             "    field: {:?}, type: {:?}, boxed: {}",
@@ -338,30 +807,62 @@ impl<'b> CodeGenerator<'_, 'b> {
             boxed
         );
 
+        if self.context.should_derive_max_encoded_len(fq_message_name)
+            && let Some(reason) =
+                self.unbounded_reason(&field.descriptor, repeated, max_len, fixed_bytes)
+        {
+            self.reject_unbounded_field(fq_message_name, field.descriptor.name(), reason);
+        }
+
         self.append_doc(fq_message_name, Some(field.descriptor.name()));
 
         self.append_field_attributes(fq_message_name, field.descriptor.name());
+        if compact && !(repeated || optional) {
+            self.push_indent();
+            self.buf.push_str("#[codec(compact)]\n");
+        }
         self.push_indent();
         self.buf.push_str("pub ");
         self.buf.push_str(&field.rust_name());
         self.buf.push_str(": ");
 
+        if boxed {
+            ty = format!("{}::boxed::Box<{}>", self.context.alloc_crate(), ty);
+        }
         if repeated {
-            self.buf.push_str(&format!("alloc::vec::Vec<"));
+            ty = match max_len {
+                Some(max_len) => bounded_vec_type(&ty, max_len),
+                None => format!("{}::vec::Vec<{}>", self.context.alloc_crate(), ty),
+            };
         } else if optional {
-            self.buf.push_str("Option<");
-        }
-        if boxed {
-            self.buf.push_str(&format!("alloc::boxed::Box<"));
+            ty = format!("Option<{}>", ty);
         }
         self.buf.push_str(&ty);
-        if boxed {
-            self.buf.push('>');
-        }
-        if repeated || optional {
-            self.buf.push('>');
-        }
         self.buf.push_str(",\n");
+
+        let scalar = if repeated {
+            None
+        } else {
+            self.scalar_default(
+                fq_message_name,
+                &field.descriptor,
+                encoding,
+                max_len,
+                fixed_bytes,
+            )?
+        };
+        let (value, accessor) = match scalar {
+            _ if repeated => (String::from("Default::default()"), None),
+            Some(scalar) if optional => (String::from("None"), scalar.accessor),
+            None if optional => (String::from("None"), None),
+            Some(scalar) => (scalar.value, None),
+            None => (String::from("Default::default()"), None),
+        };
+        Ok(MemberDefault {
+            rust_name: field.rust_name(),
+            value,
+            accessor,
+        })
     }
 
     fn append_map_field(
@@ -370,9 +871,13 @@ impl<'b> CodeGenerator<'_, 'b> {
         field: &Field,
         key: &FieldDescriptorProto,
         value: &FieldDescriptorProto,
-    ) {
-        let key_ty = self.resolve_type(key, fq_message_name);
-        let value_ty = self.resolve_type(value, fq_message_name);
+    ) -> Result<MemberDefault> {
+        let key_ty = self.resolve_type(key, fq_message_name, field.descriptor.name());
+        let (mut value_ty, value_encoding) =
+            self.resolve_encoded_type(value, fq_message_name, field.descriptor.name())?;
+        if value_encoding == IntegerEncoding::Compact {
+            value_ty = compact_type(self.config().codec_crate_path(), &value_ty);
+        }
 
         debug!(
             "    map field: {:?}, key type: {:?}, value type: {:?}",
@@ -381,20 +886,105 @@ impl<'b> CodeGenerator<'_, 'b> {
             value_ty
         );
 
+        let max_len = self
+            .context
+            .max_len(fq_message_name, field.descriptor.name());
+        if self.context.should_derive_max_encoded_len(fq_message_name) {
+            let reason = match max_len {
+                None => Some(String::from("map without a maximum length")),
+                Some(_) => {
+                    [("keys", key), ("values", value)]
+                        .into_iter()
+                        .find_map(|(role, entry)| match entry.r#type() {
+                            Type::String => Some(format!("string {} have no maximum length", role)),
+                            Type::Bytes
+                                if self
+                                    .context
+                                    .fixed_bytes(fq_message_name, field.descriptor.name(), entry)
+                                    .is_none() =>
+                            {
+                                Some(format!("bytes {} have no maximum length", role))
+                            }
+                            Type::Message | Type::Group => {
+                                self.message_unbounded_reason(entry.type_name())
+                            }
+                            _ => None,
+                        })
+                }
+            };
+            if let Some(reason) = reason {
+                self.reject_unbounded_field(fq_message_name, field.descriptor.name(), reason);
+            }
+        }
+
         self.append_doc(fq_message_name, Some(field.descriptor.name()));
 
-        let map_type = self
-            .context
-            .map_type(fq_message_name, field.descriptor.name());
+        let hash_map = max_len.is_none()
+            && self
+                .context
+                .map_type(fq_message_name, field.descriptor.name())
+                == MapType::HashMap;
+        if hash_map && self.config().type_info {
+            let fq_field_name = format!("{}.{}", fq_message_name, field.descriptor.name());
+            return Err(crate::Error::UnsupportedType {
+                location: self.error_location(&[], fq_field_name.clone()),
+                message: format!(
+                    "field `{}`: hash maps do not implement `TypeInfo`; use `Config::btree_map` for this field",
+                    fq_field_name
+                ),
+            }
+            .into());
+        }
+        if hash_map
+            && self.config().no_std_mode != NoStdMode::Std
+            && self.config().hash_map_path.is_none()
+        {
+            let fq_field_name = format!("{}.{}", fq_message_name, field.descriptor.name());
+            return Err(crate::Error::UnsupportedType {
+                location: self.error_location(&[], fq_field_name.clone()),
+                message: format!(
+                    "field `{}`: `std::collections::HashMap` is not available without `std`; use `Config::hash_map_path` to choose a hash map type, or `Config::btree_map` for this field",
+                    fq_field_name
+                ),
+            }
+            .into());
+        }
+        let map_type = match max_len {
+            Some(max_len) => format!(
+                "bounded_collections::BoundedBTreeMap<{}, {}, {}>",
+                key_ty,
+                value_ty,
+                const_u32_type(max_len)
+            ),
+            None => format!(
+                "{}<{}, {}>",
+                self.context
+                    .map_rust_type(fq_message_name, field.descriptor.name()),
+                key_ty,
+                value_ty
+            ),
+        };
         self.append_field_attributes(fq_message_name, field.descriptor.name());
+        if hash_map {
+            // `parity-scale-codec` only implements its traits for ordered maps.
+            self.context.require_support_type(SupportType::HashMapCodec);
+            self.push_indent();
+            self.buf.push_str(&format!(
+                "#[codec(encoded_as = \"{}<{}, {}>\")]\n",
+                SupportType::HashMapCodec.rust_type(),
+                key_ty,
+                value_ty
+            ));
+        }
         self.push_indent();
-        self.buf.push_str(&format!(
-            "pub {}: {}<{}, {}>,\n",
-            field.rust_name(),
-            map_type.rust_type(),
-            key_ty,
-            value_ty
-        ));
+        self.buf
+            .push_str(&format!("pub {}: {},\n", field.rust_name(), map_type));
+
+        Ok(MemberDefault {
+            rust_name: field.rust_name(),
+            value: String::from("Default::default()"),
+            accessor: None,
+        })
     }
 
     fn append_oneof_field(
@@ -418,7 +1008,11 @@ impl<'b> CodeGenerator<'_, 'b> {
         ));
     }
 
-    fn append_oneof(&mut self, fq_message_name: &str, oneof: &OneofField) {
+    fn append_oneof(&mut self, fq_message_name: &str, oneof: &OneofField) -> Result<()> {
+        let oneof_index = self
+            .context
+            .oneof_index(fq_message_name, oneof.descriptor.name());
+
         self.path.push(8);
         self.path.push(oneof.path_index);
         self.append_doc(fq_message_name, None);
@@ -428,9 +1022,23 @@ impl<'b> CodeGenerator<'_, 'b> {
         let oneof_name = format!("{}.{}", fq_message_name, oneof.descriptor.name());
         self.append_type_attributes(&oneof_name);
         self.append_enum_attributes(&oneof_name);
-        self.push_indent();
-        self.push_indent();
-        self.buf.push_str(&format!("#[derive(Encode, Decode)]\n"));
+        let oneof_fields: Vec<FieldDescriptorProto> = oneof
+            .fields
+            .iter()
+            .map(|field| field.descriptor.clone())
+            .collect();
+        let mut derives = self
+            .context
+            .derives(&oneof_name, fq_message_name, &oneof_fields);
+        let redact_debug = derives.derives.iter().any(|derive| derive == "Debug")
+            && oneof_fields.iter().any(|field| {
+                self.context
+                    .should_redact_debug(fq_message_name, field.name())
+            });
+        if redact_debug {
+            derives.derives.retain(|derive| derive != "Debug");
+        }
+        self.append_derives(&derives);
         self.push_indent();
         self.buf.push_str("pub enum ");
         self.buf.push_str(&to_upper_camel(oneof.descriptor.name()));
@@ -441,12 +1049,54 @@ impl<'b> CodeGenerator<'_, 'b> {
         for field in &oneof.fields {
             self.path.push(field.path_index);
             self.append_doc(fq_message_name, Some(field.descriptor.name()));
-            self.path.pop();
 
             self.push_indent();
             self.append_field_attributes(&oneof_name, field.descriptor.name());
 
-            let ty = self.resolve_type(&field.descriptor, fq_message_name);
+            if oneof_index == OneofIndex::FieldNumber {
+                let codec_index = u8::try_from(field.descriptor.number()).map_err(|_| {
+                    crate::Error::UnsupportedType {
+                        location: self.error_location(
+                            &[],
+                            format!("{}.{}", fq_message_name, field.descriptor.name()),
+                        ),
+                        message: format!(
+                            "oneof `{}`: field `{}` = {} does not fit in a SCALE variant index (0..=255)",
+                            oneof_name,
+                            field.descriptor.name(),
+                            field.descriptor.number()
+                        ),
+                    }
+                })?;
+                self.buf
+                    .push_str(&format!("#[codec(index = {})]\n", codec_index));
+                self.push_indent();
+            }
+
+            let (mut ty, encoding) = self.resolve_encoded_type(
+                &field.descriptor,
+                fq_message_name,
+                field.descriptor.name(),
+            )?;
+            let max_len = self
+                .context
+                .max_len(fq_message_name, field.descriptor.name());
+            let fixed_bytes = self.context.fixed_bytes(
+                fq_message_name,
+                field.descriptor.name(),
+                &field.descriptor,
+            );
+            if let (Some(max_len), Type::String | Type::Bytes, None) =
+                (max_len, field.descriptor.r#type(), fixed_bytes)
+            {
+                ty = bounded_vec_type("u8", max_len);
+            }
+            if self.context.should_derive_max_encoded_len(&oneof_name)
+                && let Some(reason) =
+                    self.unbounded_reason(&field.descriptor, false, max_len, fixed_bytes)
+            {
+                self.reject_unbounded_field(fq_message_name, field.descriptor.name(), reason);
+            }
 
             let boxed = self.context.should_box_oneof_field(
                 fq_message_name,
@@ -461,12 +1111,19 @@ impl<'b> CodeGenerator<'_, 'b> {
                 boxed
             );
 
-            if boxed {
+            if encoding == IntegerEncoding::Compact {
                 self.buf.push_str(&format!(
-                    "{}(alloc::boxed::Box<{}>),\n",
+                    "{}(#[codec(compact)] {}),\n",
                     to_upper_camel(field.descriptor.name()),
                     ty
                 ));
+            } else if boxed {
+                self.buf.push_str(&format!(
+                    "{}({}::boxed::Box<{}>),\n",
+                    to_upper_camel(field.descriptor.name()),
+                    self.context.alloc_crate(),
+                    ty
+                ));
             } else {
                 self.buf.push_str(&format!(
                     "{}({}),\n",
@@ -474,23 +1131,57 @@ impl<'b> CodeGenerator<'_, 'b> {
                     ty
                 ));
             }
+            self.path.pop();
         }
         self.depth -= 1;
         self.path.pop();
 
         self.push_indent();
         self.buf.push_str("}\n");
+
+        if redact_debug {
+            self.append_oneof_debug(fq_message_name, oneof);
+        }
+
+        Ok(())
     }
 
     fn location(&self) -> Option<&Location> {
+        self.location_at(&[])
+    }
+
+    /// Returns the source location of the element at `relative_path` from the current path.
+    fn location_at(&self, relative_path: &[i32]) -> Option<&Location> {
         let source_info = self.source_info.as_ref()?;
+        let path = [&self.path[..], relative_path].concat();
         let idx = source_info
             .location
-            .binary_search_by_key(&&self.path[..], |location| &location.path[..])
-            .unwrap();
+            .binary_search_by_key(&&path[..], |location| &location.path[..])
+            .ok()?;
         Some(&source_info.location[idx])
     }
 
+    /// Returns the location to report an error about the element at `relative_path` from the
+    /// current path, whose fully-qualified Protobuf path is `fq_path`.
+    fn error_location(&self, relative_path: &[i32], fq_path: String) -> crate::Location {
+        crate::Location {
+            file: self.file_name.clone(),
+            path: fq_path,
+            span: self
+                .location_at(relative_path)
+                .and_then(|location| Span::from_source_span(&location.span)),
+            snippet: None,
+        }
+    }
+
+    /// Records a field that prevents its message from deriving `MaxEncodedLen`.
+    fn reject_unbounded_field(&mut self, fq_message_name: &str, field_name: &str, reason: String) {
+        let fq_field_name = format!("{}.{}", fq_message_name, field_name);
+        let location = self.error_location(&[], fq_field_name.clone());
+        self.context
+            .reject_unbounded_field(location, format!("`{}` ({})", fq_field_name, reason));
+    }
+
     fn append_doc(&mut self, fq_name: &str, field_name: Option<&str>) {
         if !self.context.should_disable_comments(fq_name, field_name) {
             if let Some(comments) = self.location().map(Comments::from_location) {
@@ -499,7 +1190,7 @@ impl<'b> CodeGenerator<'_, 'b> {
         }
     }
 
-    fn append_enum(&mut self, desc: EnumDescriptorProto) {
+    fn append_enum(&mut self, desc: EnumDescriptorProto) -> Result<()> {
         debug!("  enum: {:?}", desc.name());
 
         let proto_enum_name = desc.name();
@@ -513,31 +1204,56 @@ impl<'b> CodeGenerator<'_, 'b> {
             .resolve_extern_ident(&fq_proto_enum_name)
             .is_some()
         {
-            return;
+            return Ok(());
         }
 
+        let variant_mappings =
+            build_enum_value_mappings(&enum_name, self.config().strip_enum_prefix, enum_values)
+                .map_err(|message| crate::Error::NameCollision {
+                    location: self.error_location(&[], fq_proto_enum_name.clone()),
+                    message: format!("enum `{}`: {}", fq_proto_enum_name, message),
+                })?;
+        let codec_indices = self.enum_codec_indices(&fq_proto_enum_name, &variant_mappings)?;
+
         self.append_doc(&fq_proto_enum_name, None);
         self.append_type_attributes(&fq_proto_enum_name);
         self.append_enum_attributes(&fq_proto_enum_name);
-        self.push_indent();
-
-        self.buf.push_str(&format!("#[derive(Encode, Decode)]\n"));
+        let mut derives = self
+            .context
+            .derives(&fq_proto_enum_name, &fq_proto_enum_name, &[]);
+        let enum_type = self.context.enum_type(&fq_proto_enum_name);
+        let attribute_derived = self
+            .context
+            .type_attributes(&fq_proto_enum_name)
+            .chain(self.context.enum_attributes(&fq_proto_enum_name))
+            .any(attribute_derives_default);
+        let default_number = default_variant(&variant_mappings, enum_type)
+            .filter(|_| !attribute_derived)
+            .map(|variant| variant.proto_number);
+        if default_number.is_some() {
+            derives.derives.push(String::from("Default"));
+        }
+        self.append_derives(&derives);
         self.push_indent();
         self.buf.push_str("pub enum ");
         self.buf.push_str(&enum_name);
         self.buf.push_str(" {\n");
 
-        let variant_mappings =
-            build_enum_value_mappings(&enum_name, self.config().strip_enum_prefix, enum_values);
-
         self.depth += 1;
         self.path.push(2);
-        for variant in variant_mappings.iter() {
+        for (variant, codec_index) in variant_mappings.iter().zip(codec_indices) {
             self.path.push(variant.path_idx as i32);
 
             self.append_doc(&fq_proto_enum_name, Some(variant.proto_name));
             self.append_field_attributes(&fq_proto_enum_name, variant.proto_name);
             self.push_indent();
+            self.buf
+                .push_str(&format!("#[codec(index = {})]\n", codec_index));
+            if default_number == Some(variant.proto_number) {
+                self.push_indent();
+                self.buf.push_str("#[default]\n");
+            }
+            self.push_indent();
             self.buf.push_str(&variant.generated_variant_name);
             self.buf.push_str(" = ");
             self.buf.push_str(&variant.proto_number.to_string());
@@ -635,6 +1351,67 @@ impl<'b> CodeGenerator<'_, 'b> {
         self.depth -= 1;
         self.push_indent();
         self.buf.push_str("}\n"); // End of impl
+
+        Ok(())
+    }
+
+    /// Assigns a SCALE variant index to each variant of the named enum.
+    ///
+    /// Pinned indices take precedence over the configured [`EnumIndex`] assignment.
+    fn enum_codec_indices(
+        &self,
+        fq_enum_name: &str,
+        variants: &[EnumVariantMapping<'_>],
+    ) -> Result<Vec<u8>> {
+        let index = self.context.enum_index(fq_enum_name);
+        let mut used: HashMap<u8, &str> = HashMap::new();
+        let mut indices = Vec::with_capacity(variants.len());
+
+        for (position, variant) in variants.iter().enumerate() {
+            let variant_location = || {
+                self.error_location(
+                    &[2, variant.path_idx as i32],
+                    format!("{}.{}", fq_enum_name, variant.proto_name),
+                )
+            };
+            let codec_index = match self.context.codec_index(fq_enum_name, variant.proto_name) {
+                Some(codec_index) => codec_index,
+                None => match index {
+                    EnumIndex::ProtoNumber => u8::try_from(variant.proto_number).map_err(|_| {
+                        crate::Error::UnsupportedType {
+                            location: variant_location(),
+                            message: format!(
+                                "enum `{}`: value `{}` = {} does not fit in a SCALE variant index (0..=255); use `Config::enum_index` or `Config::codec_index` to map it",
+                                fq_enum_name, variant.proto_name, variant.proto_number
+                            ),
+                        }
+                    })?,
+                    EnumIndex::DeclarationOrder => u8::try_from(position).map_err(|_| {
+                        crate::Error::UnsupportedType {
+                            location: variant_location(),
+                            message: format!(
+                                "enum `{}`: more than 256 values cannot be encoded as SCALE variants",
+                                fq_enum_name
+                            ),
+                        }
+                    })?,
+                },
+            };
+
+            if let Some(previous) = used.insert(codec_index, variant.proto_name) {
+                return Err(crate::Error::NameCollision {
+                    location: variant_location(),
+                    message: format!(
+                        "enum `{}`: SCALE variant index {} is used by both `{}` and `{}`",
+                        fq_enum_name, codec_index, previous, variant.proto_name
+                    ),
+                }
+                .into());
+            }
+            indices.push(codec_index);
+        }
+
+        Ok(indices)
     }
 
     fn push_service(&mut self, service: ServiceDescriptorProto) {
@@ -728,25 +1505,430 @@ impl<'b> CodeGenerator<'_, 'b> {
         self.buf.push_str("}\n");
     }
 
-    fn resolve_type(&self, field: &FieldDescriptorProto, fq_message_name: &str) -> String {
+    /// Resolves the Rust type of `field`.
+    ///
+    /// `field_name` is the name used to match per-field configuration. It differs from
+    /// `field.name()` for map keys and values, which are configured through the map field.
+    fn resolve_type(
+        &self,
+        field: &FieldDescriptorProto,
+        fq_message_name: &str,
+        field_name: &str,
+    ) -> String {
         match field.r#type() {
             Type::Float => String::from("f32"),
             Type::Double => String::from("f64"),
             Type::Uint32 | Type::Fixed32 => String::from("u32"),
             Type::Uint64 | Type::Fixed64 => String::from("u64"),
-            Type::Int32 | Type::Sfixed32 | Type::Sint32 | Type::Enum => String::from("i32"),
+            Type::Int32 | Type::Sfixed32 | Type::Sint32 => String::from("i32"),
             Type::Int64 | Type::Sfixed64 | Type::Sint64 => String::from("i64"),
             Type::Bool => String::from("bool"),
-            Type::String => String::from("alloc::string::String"),
-            Type::Bytes => self
-                .context
-                .bytes_type(fq_message_name, field.name())
-                .rust_type()
-                .to_owned(),
+            Type::String => format!("{}::string::String", self.context.alloc_crate()),
+            Type::Bytes => match self.context.fixed_bytes(fq_message_name, field_name, field) {
+                Some(len) => format!("[u8; {}]", len),
+                None => self.context.bytes_rust_type(fq_message_name, field_name),
+            },
+            Type::Enum => {
+                if self
+                    .context
+                    .should_type_enum_field(fq_message_name, field_name)
+                {
+                    self.resolve_ident(field.type_name())
+                } else {
+                    String::from("i32")
+                }
+            }
             Type::Group | Type::Message => self.resolve_ident(field.type_name()),
         }
     }
 
+    /// Resolves the Rust type of a field along with the SCALE encoding of integer fields.
+    ///
+    /// Zigzag encoded and bit-cast floating point fields resolve to a support type, which is
+    /// recorded so that it gets generated alongside the module.
+    fn resolve_encoded_type(
+        &mut self,
+        field: &FieldDescriptorProto,
+        fq_message_name: &str,
+        field_name: &str,
+    ) -> Result<(String, IntegerEncoding)> {
+        if matches!(field.r#type(), Type::Float | Type::Double) {
+            let ty = self.resolve_float_type(field, fq_message_name, field_name);
+            return Ok((ty, IntegerEncoding::Fixed));
+        }
+
+        let encoding = self.integer_encoding(field, fq_message_name, field_name)?;
+        let ty = match encoding {
+            IntegerEncoding::ZigZag(support_type) => {
+                self.context.require_support_type(support_type);
+                support_type.rust_type().to_owned()
+            }
+            IntegerEncoding::Fixed | IntegerEncoding::Compact => {
+                self.resolve_type(field, fq_message_name, field_name)
+            }
+        };
+        Ok((ty, encoding))
+    }
+
+    fn resolve_float_type(
+        &mut self,
+        field: &FieldDescriptorProto,
+        fq_message_name: &str,
+        field_name: &str,
+    ) -> String {
+        let double = field.r#type() == Type::Double;
+        match self.config().float_strategy.clone() {
+            FloatStrategy::BitCast => {
+                let support_type = if double {
+                    SupportType::F64Bits
+                } else {
+                    SupportType::F32Bits
+                };
+                self.context.require_support_type(support_type);
+                support_type.rust_type().to_owned()
+            }
+            FloatStrategy::Wrapper { f32, f64 } => {
+                if double {
+                    f64
+                } else {
+                    f32
+                }
+            }
+            FloatStrategy::Reject => {
+                let location =
+                    self.error_location(&[], format!("{}.{}", fq_message_name, field_name));
+                self.context.reject_float_field(location);
+                self.resolve_type(field, fq_message_name, field_name)
+            }
+        }
+    }
+
+    /// Returns the default of a singular field that is not a message: the proto2 declared
+    /// default, or the zero value otherwise. Enums default to their first variant.
+    ///
+    /// Returns `None` for message fields and for enums whose descriptor is not known, which use
+    /// `Default::default()` and get no accessor.
+    fn scalar_default(
+        &self,
+        fq_message_name: &str,
+        field: &FieldDescriptorProto,
+        encoding: IntegerEncoding,
+        max_len: Option<u32>,
+        fixed_bytes: Option<usize>,
+    ) -> Result<Option<ScalarDefault>> {
+        let declared = field.default_value.as_deref();
+        let fq_field_name = || format!("{}.{}", fq_message_name, field.name());
+        let check_max_len = |len: usize| -> Result<()> {
+            match max_len {
+                Some(max_len) if len > max_len as usize => Err(crate::Error::UnsupportedType {
+                    location: self.error_location(&[], fq_field_name()),
+                    message: format!(
+                        "field `{}`: the default value has {} bytes, but the field holds at most {}",
+                        fq_field_name(),
+                        len,
+                        max_len
+                    ),
+                }
+                .into()),
+                _ => Ok(()),
+            }
+        };
+        let declared_or_default = |literal: String| match declared {
+            Some(_) => literal,
+            None => String::from("Default::default()"),
+        };
+        let (value, accessor) = match field.r#type() {
+            Type::Message | Type::Group => return Ok(None),
+            Type::Float | Type::Double => {
+                let double = field.r#type() == Type::Double;
+                let float = if double { "f64" } else { "f32" };
+                let literal = match declared {
+                    Some("inf") => format!("{}::INFINITY", float),
+                    Some("-inf") => format!("{}::NEG_INFINITY", float),
+                    Some("nan") => format!("{}::NAN", float),
+                    Some(declared) => format!("{}{}", declared, float),
+                    None => format!("0{}", float),
+                };
+                match self.config().float_strategy {
+                    FloatStrategy::BitCast => {
+                        let support_type = if double {
+                            SupportType::F64Bits
+                        } else {
+                            SupportType::F32Bits
+                        };
+                        (
+                            declared_or_default(format!(
+                                "{}::from({})",
+                                support_type.rust_type(),
+                                literal
+                            )),
+                            Some(Accessor::new(float, "value.get()", literal)),
+                        )
+                    }
+                    // The wrapper type is only known to convert from the primitive.
+                    FloatStrategy::Wrapper { .. } => (
+                        declared_or_default(format!("From::from({})", literal)),
+                        None,
+                    ),
+                    FloatStrategy::Reject => (
+                        declared_or_default(literal.clone()),
+                        Some(Accessor::new(float, "*value", literal)),
+                    ),
+                }
+            }
+            Type::Bool => {
+                let literal = declared.unwrap_or("false").to_owned();
+                (
+                    declared_or_default(literal.clone()),
+                    Some(Accessor::new("bool", "*value", literal)),
+                )
+            }
+            Type::String => {
+                let text = declared.unwrap_or_default();
+                if max_len.is_some() {
+                    check_max_len(text.len())?;
+                    let literal = byte_string_literal(text.as_bytes());
+                    (
+                        declared_or_default(format!(
+                            "bounded_collections::BoundedVec::truncate_from({}.to_vec())",
+                            literal
+                        )),
+                        Some(Accessor::new("&[u8]", "&value[..]", literal)),
+                    )
+                } else {
+                    (
+                        declared_or_default(format!(
+                            "{}::string::String::from({:?})",
+                            self.context.alloc_crate(),
+                            text
+                        )),
+                        Some(Accessor::new(
+                            "&str",
+                            "value.as_str()",
+                            format!("{:?}", text),
+                        )),
+                    )
+                }
+            }
+            Type::Bytes => {
+                let bytes = match declared {
+                    Some(declared) => unescape_c_escape_string(declared).map_err(|error| {
+                        crate::Error::InvalidSyntax {
+                            location: self.error_location(&[], fq_field_name()),
+                            message: format!("field `{}`: {}", fq_field_name(), error),
+                        }
+                    })?,
+                    None => Vec::new(),
+                };
+                let literal = byte_string_literal(&bytes);
+                match fixed_bytes {
+                    Some(len) if declared.is_none() => (
+                        format!("[0; {}]", len),
+                        Some(Accessor::new(
+                            "&[u8]",
+                            "&value[..]",
+                            format!("&[0; {}]", len),
+                        )),
+                    ),
+                    Some(len) if bytes.len() != len => {
+                        return Err(crate::Error::UnsupportedType {
+                            location: self.error_location(&[], fq_field_name()),
+                            message: format!(
+                                "field `{}`: the default value has {} bytes, but the field holds exactly {}",
+                                fq_field_name(),
+                                bytes.len(),
+                                len
+                            ),
+                        }
+                        .into());
+                    }
+                    Some(_) => (
+                        format!("*{}", literal),
+                        Some(Accessor::new("&[u8]", "&value[..]", literal)),
+                    ),
+                    None => {
+                        check_max_len(bytes.len())?;
+                        let value = if max_len.is_some() {
+                            format!(
+                                "bounded_collections::BoundedVec::truncate_from({}.to_vec())",
+                                literal
+                            )
+                        } else {
+                            match self.context.bytes_type(fq_message_name, field.name()) {
+                                BytesType::Vec => format!("{}.to_vec()", literal),
+                                BytesType::Bytes => format!("From::from({}.to_vec())", literal),
+                            }
+                        };
+                        (
+                            declared_or_default(value),
+                            Some(Accessor::new("&[u8]", "&value[..]", literal)),
+                        )
+                    }
+                }
+            }
+            Type::Enum => {
+                let Some(descriptor) = self.context.enum_descriptor(field.type_name()) else {
+                    return Ok(None);
+                };
+                let variants = build_enum_value_mappings(
+                    &to_upper_camel(descriptor.name()),
+                    self.config().strip_enum_prefix,
+                    &descriptor.value,
+                )
+                .map_err(|message| crate::Error::NameCollision {
+                    location: self.error_location(&[], fq_field_name()),
+                    message: format!("enum `{}`: {}", field.type_name(), message),
+                })?;
+                let number = match declared {
+                    Some(declared) => descriptor
+                        .value
+                        .iter()
+                        .find(|value| value.name() == declared)
+                        .map(EnumValueDescriptorProto::number),
+                    None => default_variant(&variants, self.context.enum_type(field.type_name()))
+                        .map(|variant| variant.proto_number),
+                };
+                let Some(variant) =
+                    number.and_then(|number| variants.iter().find(|v| v.proto_number == number))
+                else {
+                    return Ok(None);
+                };
+                if self
+                    .context
+                    .should_type_enum_field(fq_message_name, field.name())
+                {
+                    let enum_type = self.resolve_ident(field.type_name());
+                    let path = format!("{}::{}", enum_type, variant.generated_variant_name);
+                    (
+                        path.clone(),
+                        Some(Accessor::new(
+                            format!("&{}", enum_type),
+                            "value",
+                            format!("&{}", path),
+                        )),
+                    )
+                } else {
+                    let number = variant.proto_number.to_string();
+                    (number.clone(), Some(Accessor::new("i32", "*value", number)))
+                }
+            }
+            _ => {
+                let literal = declared.unwrap_or("0").to_owned();
+                let ty = self.resolve_type(field, fq_message_name, field.name());
+                match encoding {
+                    IntegerEncoding::ZigZag(support_type) => (
+                        declared_or_default(format!("{}({})", support_type.rust_type(), literal)),
+                        Some(Accessor::new(ty, "value.0", literal)),
+                    ),
+                    IntegerEncoding::Compact => (
+                        declared_or_default(literal.clone()),
+                        Some(Accessor::new(ty, "value.0", literal)),
+                    ),
+                    IntegerEncoding::Fixed => (
+                        declared_or_default(literal.clone()),
+                        Some(Accessor::new(ty, "*value", literal)),
+                    ),
+                }
+            }
+        };
+        Ok(Some(ScalarDefault { value, accessor }))
+    }
+
+    /// Returns why a field prevents its type from deriving `MaxEncodedLen`, if it does.
+    fn unbounded_reason(
+        &self,
+        field: &FieldDescriptorProto,
+        repeated: bool,
+        max_len: Option<u32>,
+        fixed_bytes: Option<usize>,
+    ) -> Option<String> {
+        if repeated && max_len.is_none() {
+            return Some(String::from("repeated field without a maximum length"));
+        }
+        let proto_type = match field.r#type() {
+            Type::String => "string",
+            Type::Bytes if fixed_bytes.is_some() => return None,
+            Type::Bytes => "bytes",
+            Type::Message | Type::Group => return self.message_unbounded_reason(field.type_name()),
+            _ => return None,
+        };
+        if repeated {
+            Some(format!("{} elements have no maximum length", proto_type))
+        } else if max_len.is_none() {
+            Some(format!("{} without a maximum length", proto_type))
+        } else {
+            None
+        }
+    }
+
+    fn message_unbounded_reason(&self, type_name: &str) -> Option<String> {
+        if self.context.resolve_extern_ident(type_name).is_some()
+            || self.context.should_derive_max_encoded_len(type_name)
+        {
+            None
+        } else {
+            Some(format!("`{}` does not derive `MaxEncodedLen`", type_name))
+        }
+    }
+
+    fn integer_encoding(
+        &self,
+        field: &FieldDescriptorProto,
+        fq_message_name: &str,
+        field_name: &str,
+    ) -> Result<IntegerEncoding> {
+        if self
+            .context
+            .should_compact_field(fq_message_name, field_name, field)
+        {
+            return Ok(IntegerEncoding::Compact);
+        }
+        if matches!(
+            field.r#type(),
+            Type::Int32
+                | Type::Int64
+                | Type::Sint32
+                | Type::Sint64
+                | Type::Sfixed32
+                | Type::Sfixed64
+        ) && self
+            .context
+            .is_compact_field_named(fq_message_name, field_name)
+        {
+            return Err(crate::Error::UnsupportedType {
+                location: self.error_location(&[], format!("{}.{}", fq_message_name, field_name)),
+                message: format!(
+                    "field `{}.{}`: signed integers have no SCALE compact encoding; remove the field from `Config::compact`",
+                    fq_message_name, field_name
+                ),
+            }
+            .into());
+        }
+        if self.config().encoding_profile != EncodingProfile::ProtoTypes {
+            return Ok(IntegerEncoding::Fixed);
+        }
+
+        let (proto_type, support_type) = match field.r#type() {
+            Type::Int32 => ("int32", SupportType::ZigZagI32),
+            Type::Sint32 => ("sint32", SupportType::ZigZagI32),
+            Type::Int64 => ("int64", SupportType::ZigZagI64),
+            Type::Sint64 => ("sint64", SupportType::ZigZagI64),
+            _ => return Ok(IntegerEncoding::Fixed),
+        };
+        match self.config().signed_varint {
+            SignedVarint::ZigZag => Ok(IntegerEncoding::ZigZag(support_type)),
+            SignedVarint::FixedWidth => Ok(IntegerEncoding::Fixed),
+            SignedVarint::Reject => Err(crate::Error::UnsupportedType {
+                location: self.error_location(&[], format!("{}.{}", fq_message_name, field_name)),
+                message: format!(
+                    "field `{}.{}`: signed varint type `{}` has no SCALE compact encoding; use `Config::signed_varint` to choose a strategy",
+                    fq_message_name, field_name, proto_type
+                ),
+            }
+            .into()),
+        }
+    }
+
     fn resolve_ident(&self, pb_ident: &str) -> String {
         // protoc should always give fully qualified identifiers.
         assert_eq!(".", &pb_ident[..1]);
@@ -785,21 +1967,6 @@ impl<'b> CodeGenerator<'_, 'b> {
             .join("::")
     }
 
-    fn optional(&self, field: &FieldDescriptorProto) -> bool {
-        if field.proto3_optional.unwrap_or(false) {
-            return true;
-        }
-
-        if field.label() != Label::Optional {
-            return false;
-        }
-
-        match field.r#type() {
-            Type::Message => true,
-            _ => self.syntax == Syntax::Proto2,
-        }
-    }
-
     /// Returns the fully-qualified name, starting with a dot
     fn fq_name(&self, message_name: &str) -> String {
         format!(
@@ -813,6 +1980,41 @@ impl<'b> CodeGenerator<'_, 'b> {
     }
 }
 
+/// The `bounded-collections` type for a vector of at most `max_len` elements.
+fn bounded_vec_type(element: &str, max_len: u32) -> String {
+    format!(
+        "bounded_collections::BoundedVec<{}, {}>",
+        element,
+        const_u32_type(max_len)
+    )
+}
+
+fn const_u32_type(value: u32) -> String {
+    format!("bounded_collections::ConstU32<{}>", value)
+}
+
+/// Formats bytes as a Rust byte string literal.
+fn byte_string_literal(bytes: &[u8]) -> String {
+    let mut literal = String::from("b\"");
+    for &byte in bytes {
+        match byte {
+            b'"' | b'\\' => {
+                literal.push('\\');
+                literal.push(char::from(byte));
+            }
+            b' '..=b'~' => literal.push(char::from(byte)),
+            _ => literal.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Wraps an integer type in SCALE's `Compact` encoding wrapper.
+fn compact_type(codec_crate: &str, ty: &str) -> String {
+    format!("{}::Compact<{}>", codec_crate, ty)
+}
+
 struct EnumVariantMapping<'a> {
     path_idx: usize,
     proto_name: &'a str,
@@ -820,11 +2022,26 @@ struct EnumVariantMapping<'a> {
     generated_variant_name: String,
 }
 
+/// Returns the variant an enum defaults to: the zero value of an open enum, and the first
+/// declared value of a closed enum.
+fn default_variant<'a, 'b>(
+    variants: &'b [EnumVariantMapping<'a>],
+    enum_type: EnumType,
+) -> Option<&'b EnumVariantMapping<'a>> {
+    match enum_type {
+        EnumType::Open => variants
+            .iter()
+            .find(|variant| variant.proto_number == 0)
+            .or_else(|| variants.first()),
+        EnumType::Closed => variants.first(),
+    }
+}
+
 fn build_enum_value_mappings<'a>(
     generated_enum_name: &str,
     do_strip_enum_prefix: bool,
     enum_values: &'a [EnumValueDescriptorProto],
-) -> Vec<EnumVariantMapping<'a>> {
+) -> std::result::Result<Vec<EnumVariantMapping<'a>>, String> {
     let mut numbers = HashSet::new();
     let mut generated_names = HashMap::new();
     let mut mappings = Vec::new();
@@ -844,12 +2061,12 @@ fn build_enum_value_mappings<'a>(
 
         if let Some(old_v) = generated_names.insert(generated_variant_name.to_owned(), value.name())
         {
-            panic!(
-                "Generated enum variant names overlap: `{}` variant name to be used both by `{}` and `{}` ProtoBuf enum values",
+            return Err(format!(
+                "generated variant names overlap: `{}` would be used by both `{}` and `{}`",
                 generated_variant_name,
                 old_v,
                 value.name()
-            );
+            ));
         }
 
         mappings.push(EnumVariantMapping {
@@ -859,5 +2076,5 @@ fn build_enum_value_mappings<'a>(
             generated_variant_name,
         })
     }
-    mappings
+    Ok(mappings)
 }
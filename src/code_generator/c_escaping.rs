@@ -1 +1,120 @@
+/// Unescapes a C-escaped string, as protoc writes the default values of `bytes` fields.
+///
+/// Based on [`google::protobuf::UnescapeCEscapeString`][1].
+///
+/// [1]: https://github.com/google/protobuf/blob/3.3.x/src/google/protobuf/stubs/strutil.cc#L312-L322
+pub(super) fn unescape_c_escape_string(s: &str) -> Result<Vec<u8>, String> {
+    let src = s.as_bytes();
+    let len = src.len();
+    let mut dst = Vec::new();
 
+    let mut p = 0;
+
+    while p < len {
+        if src[p] != b'\\' {
+            dst.push(src[p]);
+            p += 1;
+            continue;
+        }
+
+        p += 1;
+        if p == len {
+            return Err(format!(
+                "invalid c-escaped default binary value ({}): ends with '\\'",
+                s
+            ));
+        }
+        match src[p] {
+            b'a' => dst.push(0x07),
+            b'b' => dst.push(0x08),
+            b'f' => dst.push(0x0C),
+            b'n' => dst.push(0x0A),
+            b'r' => dst.push(0x0D),
+            b't' => dst.push(0x09),
+            b'v' => dst.push(0x0B),
+            b'\\' => dst.push(0x5C),
+            b'?' => dst.push(0x3F),
+            b'\'' => dst.push(0x27),
+            b'"' => dst.push(0x22),
+            b'0'..=b'7' => {
+                let mut octal: u16 = 0;
+                for _ in 0..3 {
+                    if p < len && (b'0'..=b'7').contains(&src[p]) {
+                        octal = octal * 8 + u16::from(src[p] - b'0');
+                        p += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let byte = u8::try_from(octal).map_err(|_| {
+                    format!(
+                        "invalid c-escaped default binary value ({}): octal escape out of range",
+                        s
+                    )
+                })?;
+                dst.push(byte);
+                continue;
+            }
+            b'x' | b'X' => {
+                let byte = s
+                    .get(p + 1..p + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| {
+                        format!(
+                            "invalid c-escaped default binary value ({}): invalid hex escape",
+                            s
+                        )
+                    })?;
+                dst.push(byte);
+                p += 3;
+                continue;
+            }
+            _ => {
+                return Err(format!(
+                    "invalid c-escaped default binary value ({}): invalid escape",
+                    s
+                ));
+            }
+        }
+        p += 1;
+    }
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape_c_escape_string() {
+        assert_eq!(
+            Ok(b"hello world".to_vec()),
+            unescape_c_escape_string("hello world")
+        );
+
+        assert_eq!(Ok(b"\0".to_vec()), unescape_c_escape_string(r"\0"));
+
+        assert_eq!(
+            Ok([0o012, 0o156].to_vec()),
+            unescape_c_escape_string(r"\012\156")
+        );
+        assert_eq!(
+            Ok([0x01, 0x02].to_vec()),
+            unescape_c_escape_string(r"\x01\x02")
+        );
+
+        assert_eq!(
+            Ok(b"\0\x01\x07\x08\x0C\n\r\t\x0B\\\'\"\xFE".to_vec()),
+            unescape_c_escape_string(r#"\0\001\a\b\f\n\r\t\v\\\'\"\xfe"#)
+        );
+    }
+
+    #[test]
+    fn test_unescape_c_escape_string_errors() {
+        assert!(unescape_c_escape_string(r"\").is_err());
+        assert!(unescape_c_escape_string(r"\x").is_err());
+        assert!(unescape_c_escape_string(r"\xZZ").is_err());
+        assert!(unescape_c_escape_string(r"\777").is_err());
+        assert!(unescape_c_escape_string(r"\q").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{Config, MapType};
+
+/// A helper type emitted once into every generated module that refers to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SupportType {
+    ZigZagI32,
+    ZigZagI64,
+    F32Bits,
+    F64Bits,
+    HashMapCodec,
+}
+
+const ZIGZAG_TEMPLATE: &str = r#"
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct $NAME(pub $INT);
+
+impl $NAME {
+    fn zigzag(self) -> $UINT {
+        ((self.0 << 1) ^ (self.0 >> $SHIFT)) as $UINT
+    }
+}
+
+impl Encode for $NAME {
+    fn size_hint(&self) -> usize {
+        $CODEC::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: $CODEC::Output + ?Sized>(&self, dest: &mut T) {
+        $CODEC::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl $CODEC::EncodeLike for $NAME {}
+
+impl Decode for $NAME {
+    fn decode<I: $CODEC::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, $CODEC::Error> {
+        let value = $CODEC::Compact::<$UINT>::decode(input)?.0;
+        Ok($NAME((value >> 1) as $INT ^ -((value & 1) as $INT)))
+    }
+}
+"#;
+
+const ZIGZAG_TYPE_INFO_TEMPLATE: &str = r#"
+impl $SCALE_INFO::TypeInfo for $NAME {
+    type Identity = Self;
+
+    fn type_info() -> $SCALE_INFO::Type {
+        $SCALE_INFO::Type::builder()
+            .path($SCALE_INFO::Path::new("$NAME", module_path!()))
+            .composite(
+                $SCALE_INFO::build::Fields::unnamed()
+                    .field(|f| f.compact::<$UINT>().type_name("$UINT")),
+            )
+    }
+}
+"#;
+
+const ZIGZAG_MAX_ENCODED_LEN_TEMPLATE: &str = r#"
+impl $CODEC::MaxEncodedLen for $NAME {
+    fn max_encoded_len() -> usize {
+        $CODEC::Compact::<$UINT>::max_encoded_len()
+    }
+}
+"#;
+
+const FLOAT_BITS_TEMPLATE: &str = r#"
+/// A `$FLOAT` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode$DERIVES)]
+$ATTRIBUTES
+pub struct $NAME(pub $UINT);
+
+impl $NAME {
+    /// Returns the floating point value.
+    pub fn get(self) -> $FLOAT {
+        $FLOAT::from_bits(self.0)
+    }
+}
+
+impl From<$FLOAT> for $NAME {
+    fn from(value: $FLOAT) -> Self {
+        $NAME(value.to_bits())
+    }
+}
+
+impl From<$NAME> for $FLOAT {
+    fn from(value: $NAME) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for $NAME {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
+"#;
+
+const HASH_MAP_CODEC_TEMPLATE: &str = r#"
+/// Encodes a hash map field like a `BTreeMap`, with the entries sorted by key so that the
+/// encoding is deterministic. Used through `#[codec(encoded_as = "..")]`.
+pub struct HashMapCodec<K, V>(pub $ALLOC::vec::Vec<(K, V)>);
+
+/// The borrowed form of [`HashMapCodec`] used for encoding.
+pub struct HashMapCodecRef<'a, K, V>($ALLOC::vec::Vec<(&'a K, &'a V)>);
+
+impl<'a, K: Ord, V, S> From<&'a $MAP<K, V, S>> for HashMapCodecRef<'a, K, V> {
+    fn from(map: &'a $MAP<K, V, S>) -> Self {
+        let mut entries: $ALLOC::vec::Vec<_> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        HashMapCodecRef(entries)
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for HashMapCodecRef<'_, K, V> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<T: $CODEC::Output + ?Sized>(&self, dest: &mut T) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<'a, K: Ord + Encode + 'a, V: Encode + 'a, S: 'a> $CODEC::EncodeAsRef<'a, $MAP<K, V, S>>
+    for HashMapCodec<K, V>
+{
+    type RefType = HashMapCodecRef<'a, K, V>;
+}
+
+impl<K: Decode, V: Decode> Decode for HashMapCodec<K, V> {
+    fn decode<I: $CODEC::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, $CODEC::Error> {
+        Ok(HashMapCodec(Decode::decode(input)?))
+    }
+}
+
+impl<K: Eq + core::hash::Hash, V, S: core::hash::BuildHasher + Default> From<HashMapCodec<K, V>>
+    for $MAP<K, V, S>
+{
+    fn from(codec: HashMapCodec<K, V>) -> Self {
+        codec.0.into_iter().collect()
+    }
+}
+"#;
+
+impl SupportType {
+    /// The name of the generated type.
+    pub(crate) fn rust_type(self) -> &'static str {
+        match self {
+            SupportType::ZigZagI32 => "ZigZagI32",
+            SupportType::ZigZagI64 => "ZigZagI64",
+            SupportType::F32Bits => "F32Bits",
+            SupportType::F64Bits => "F64Bits",
+            SupportType::HashMapCodec => "HashMapCodec",
+        }
+    }
+
+    /// Appends the definition of the type to a module's generated code.
+    ///
+    /// The type implements the configured scale-info crate's `TypeInfo` and `MaxEncodedLen` when
+    /// the configuration makes use of them.
+    pub(crate) fn append(self, buf: &mut String, config: &Config) {
+        let type_info = config.type_info;
+        let alloc_crate = config.no_std_mode.alloc_crate();
+        let max_encoded_len = !config.max_encoded_len.is_empty();
+        let code = match self {
+            SupportType::ZigZagI32 => zigzag("i32", "u32", "31", type_info, max_encoded_len),
+            SupportType::ZigZagI64 => zigzag("i64", "u64", "63", type_info, max_encoded_len),
+            SupportType::F32Bits => FLOAT_BITS_TEMPLATE
+                .replace("$FLOAT", "f32")
+                .replace("$UINT", "u32"),
+            SupportType::F64Bits => FLOAT_BITS_TEMPLATE
+                .replace("$FLOAT", "f64")
+                .replace("$UINT", "u64"),
+            SupportType::HashMapCodec => HASH_MAP_CODEC_TEMPLATE
+                .replace(
+                    "$MAP",
+                    &config
+                        .hash_map_path
+                        .clone()
+                        .unwrap_or_else(|| MapType::HashMap.rust_type(alloc_crate)),
+                )
+                .replace("$ALLOC", alloc_crate),
+        };
+        let mut derives = String::new();
+        if max_encoded_len {
+            derives.push_str(", $CODEC::MaxEncodedLen");
+        }
+        if type_info {
+            derives.push_str(", TypeInfo");
+        }
+        let mut attributes = String::new();
+        if let Some(codec_crate) = &config.codec_crate {
+            attributes.push_str(&format!("#[codec(crate = {})]\n", codec_crate));
+        }
+        if let (true, Some(scale_info_crate)) = (type_info, &config.scale_info_crate) {
+            attributes.push_str(&format!("#[scale_info(crate = {})]\n", scale_info_crate));
+        }
+        buf.push_str(
+            &code
+                .replace("$ATTRIBUTES\n", &attributes)
+                .replace("$DERIVES", &derives)
+                .replace("$NAME", self.rust_type())
+                .replace("$CODEC", config.codec_crate_path())
+                .replace("$SCALE_INFO", config.scale_info_crate_path()),
+        );
+    }
+}
+
+fn zigzag(int: &str, uint: &str, shift: &str, type_info: bool, max_encoded_len: bool) -> String {
+    let mut code = String::from(ZIGZAG_TEMPLATE);
+    if type_info {
+        code.push_str(ZIGZAG_TYPE_INFO_TEMPLATE);
+    }
+    if max_encoded_len {
+        code.push_str(ZIGZAG_MAX_ENCODED_LEN_TEMPLATE);
+    }
+    code.replace("$UINT", uint)
+        .replace("$INT", int)
+        .replace("$SHIFT", shift)
+}
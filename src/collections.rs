@@ -2,10 +2,11 @@
 #[non_exhaustive]
 #[derive(Default, Clone, Copy, Debug, PartialEq)]
 pub(crate) enum MapType {
-    /// The [`alloc::collections::BTreeMap`] type.
-    #[default]
+    /// The [`std::collections::HashMap`] type, or the type set with
+    /// [`Config::hash_map_path`](crate::Config::hash_map_path).
     HashMap,
     /// The [`alloc::collections::BTreeMap`] type.
+    #[default]
     BTreeMap,
 }
 
@@ -20,22 +21,51 @@ pub(crate) enum BytesType {
     Bytes,
 }
 
+/// Which standard library crates the generated code relies on.
+#[non_exhaustive]
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub enum NoStdMode {
+    /// Generated code only uses the `alloc` crate. Every generated file declares
+    /// `extern crate alloc;`, or the include file does once when
+    /// [`Config::include_file`](crate::Config::include_file) is set, unless
+    /// [`Config::extern_crate_alloc`](crate::Config::extern_crate_alloc) is disabled. Derives that
+    /// need `std` are left out.
+    #[default]
+    Alloc,
+    /// Generated code uses `std` paths and declares no crates. Derives that need `std` are
+    /// emitted.
+    Std,
+    /// Like [`NoStdMode::Alloc`], with the derives that need `std` emitted behind
+    /// `#[cfg_attr(feature = "..", derive(..))]` on the named feature of the including crate.
+    Feature(String),
+}
+
+impl NoStdMode {
+    /// The crate providing collections and boxes to the generated code.
+    pub(crate) fn alloc_crate(&self) -> &'static str {
+        match self {
+            NoStdMode::Std => "std",
+            NoStdMode::Alloc | NoStdMode::Feature(_) => "alloc",
+        }
+    }
+}
+
 impl MapType {
     /// The fully-qualified Rust type corresponding to the map type.
-    pub fn rust_type(&self) -> &'static str {
+    pub fn rust_type(&self, alloc_crate: &str) -> String {
         match self {
-            MapType::HashMap => "alloc::collections::BTreeMap",
-            MapType::BTreeMap => "alloc::collections::BTreeMap",
+            MapType::HashMap => String::from("std::collections::HashMap"),
+            MapType::BTreeMap => format!("{}::collections::BTreeMap", alloc_crate),
         }
     }
 }
 
 impl BytesType {
     /// The fully-qualified Rust type corresponding to the bytes type.
-    pub fn rust_type(&self) -> &'static str {
+    pub fn rust_type(&self, alloc_crate: &str) -> String {
         match self {
-            BytesType::Vec => "alloc::vec::Vec<u8>",
-            BytesType::Bytes => "Bytes",
+            BytesType::Vec => format!("{}::vec::Vec<u8>", alloc_crate),
+            BytesType::Bytes => String::from("::bytes::Bytes"),
         }
     }
 }
@@ -1,22 +1,33 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::default;
 use std::env;
 use std::fmt;
 use std::fs;
 use std::io::{Error, ErrorKind, Result, Write};
-use std::path::{Path, PathBuf};
+use std::path::{self, Path, PathBuf};
 
 use log::trace;
 
+use prost::Message;
 use prost_types::{FileDescriptorProto, FileDescriptorSet};
 
 use crate::BytesType;
+use crate::EncodingProfile;
+use crate::EnumIndex;
+use crate::FieldOrder;
+use crate::FloatStrategy;
+use crate::Manifest;
+use crate::ManifestFile;
 use crate::MapType;
 use crate::Module;
+use crate::NoStdMode;
+use crate::OneofIndex;
 use crate::ServiceGenerator;
-use crate::code_generator::CodeGenerator;
+use crate::SignedVarint;
+use crate::code_generator::{CodeGenerator, SupportType};
 use crate::context::Context;
 use crate::extern_paths::ExternPaths;
+use crate::features::DescriptorFeatures;
 use crate::message_graph::MessageGraph;
 use crate::path::PathMap;
 
@@ -26,12 +37,32 @@ use crate::path::PathMap;
 pub struct Config {
     pub(crate) service_generator: Option<Box<dyn ServiceGenerator>>,
     pub(crate) map_type: PathMap<MapType>,
+    pub(crate) hash_map_path: Option<String>,
     pub(crate) bytes_type: PathMap<BytesType>,
+    pub(crate) bytes_path: Option<String>,
     pub(crate) type_attributes: PathMap<String>,
     pub(crate) message_attributes: PathMap<String>,
     pub(crate) enum_attributes: PathMap<String>,
     pub(crate) field_attributes: PathMap<String>,
     pub(crate) boxed: PathMap<()>,
+    pub(crate) typed_enums: PathMap<()>,
+    pub(crate) compact: PathMap<()>,
+    pub(crate) derives: PathMap<Vec<String>>,
+    pub(crate) type_info: bool,
+    pub(crate) max_len: PathMap<u32>,
+    pub(crate) fixed_bytes: PathMap<usize>,
+    pub(crate) max_encoded_len: PathMap<()>,
+    pub(crate) encoding_profile: EncodingProfile,
+    pub(crate) signed_varint: SignedVarint,
+    pub(crate) float_strategy: FloatStrategy,
+    pub(crate) no_std_mode: NoStdMode,
+    pub(crate) extern_crate_alloc: bool,
+    pub(crate) codec_crate: Option<String>,
+    pub(crate) scale_info_crate: Option<String>,
+    pub(crate) enum_index: PathMap<EnumIndex>,
+    pub(crate) codec_index: PathMap<u8>,
+    pub(crate) oneof_index: PathMap<OneofIndex>,
+    pub(crate) field_order: FieldOrder,
     pub(crate) strip_enum_prefix: bool,
     pub(crate) out_dir: Option<PathBuf>,
     pub(crate) extern_paths: Vec<(String, String)>,
@@ -40,6 +71,10 @@ pub struct Config {
     pub(crate) disable_comments: PathMap<()>,
     pub(crate) skip_debug: PathMap<()>,
     pub(crate) include_file: Option<PathBuf>,
+    pub(crate) protos: Vec<PathBuf>,
+    pub(crate) includes: Vec<PathBuf>,
+    pub(crate) emit_rerun_if_changed: bool,
+    pub(crate) config_file: Option<PathBuf>,
 }
 
 impl Config {
@@ -51,6 +86,11 @@ impl Config {
     /// Configure the code generator to generate Rust [`BTreeMap`][1] fields for Protobuf
     /// [`map`][2] type fields.
     ///
+    /// `BTreeMap` is the default map type, so this only overrides paths matched by
+    /// [`hash_map`](Self::hash_map), and has no effect otherwise. The more specific path wins
+    /// when both match a field. Code that relied on this method to opt out of hash maps, which
+    /// were the default in `prost-build`, can drop the call.
+    ///
     /// # Arguments
     ///
     /// **`paths`** - paths to specific fields, messages, or packages which should use a Rust
@@ -58,7 +98,8 @@ impl Config {
     /// name (not the generated Rust type name). Paths with a leading `.` are treated as fully
     /// qualified names. Paths without a leading `.` are treated as relative, and are suffix
     /// matched on the fully qualified field name. If a Protobuf map field matches any of the
-    /// paths, a Rust `BTreeMap` field is generated instead of the default [`HashMap`][3].
+    /// paths, a Rust `BTreeMap` field is generated instead of a [`HashMap`][3], even when a less
+    /// specific `hash_map` path matches it.
     ///
     /// The matching is done on the Protobuf names, before converting to Rust-friendly casing
     /// standards.
@@ -76,7 +117,7 @@ impl Config {
     /// // Match all map fields in a package.
     /// config.btree_map(&[".my_messages"]);
     ///
-    /// // Match all map fields. Specially useful in `no_std` contexts.
+    /// // Match all map fields.
     /// config.btree_map(&["."]);
     ///
     /// // Match all map fields in a nested message.
@@ -101,7 +142,10 @@ impl Config {
         I: IntoIterator<Item = S>,
         S: AsRef<str>,
     {
-        self.map_type.clear();
+        // Keep the paths set by `hash_map`, so that both can refine each other.
+        self.map_type
+            .matchers
+            .retain(|(_, map_type)| *map_type != MapType::BTreeMap);
         for matcher in paths {
             self.map_type
                 .insert(matcher.as_ref().to_string(), MapType::BTreeMap);
@@ -109,9 +153,85 @@ impl Config {
         self
     }
 
+    /// Configure the code generator to generate hash map fields for Protobuf [`map`][1] type
+    /// fields.
+    ///
+    /// Matched fields use [`std::collections::HashMap`] by default, or the map type set with
+    /// [`hash_map_path`](Self::hash_map_path), which is required unless the
+    /// [`no_std_mode`](Self::no_std_mode) is [`NoStdMode::Std`]. `parity-scale-codec` has no
+    /// hash map support, so the fields are encoded through a `HashMapCodec` support type
+    /// generated alongside the messages. It encodes the entries sorted by key, which gives the
+    /// same bytes as a `BTreeMap` field.
+    ///
+    /// Messages with hash map fields cannot derive `Hash`, and the field type has no
+    /// `scale_info::TypeInfo` implementation, so code generation fails for matched fields when
+    /// [`type_info`](Self::type_info) is enabled. Fields bounded by [`max_len`](Self::max_len)
+    /// always use `BoundedBTreeMap`.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - paths to specific fields, messages, or packages which should use a hash map
+    /// for Protobuf `map` fields. For details about matching fields see
+    /// [`btree_map`](Self::btree_map). The more specific path wins when a field is matched by
+    /// both.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Use hash maps everywhere except in the `storage` package.
+    /// config.hash_map(&["."]).btree_map(&[".storage"]);
+    /// ```
+    ///
+    /// [1]: https://developers.google.com/protocol-buffers/docs/proto3#maps
+    pub fn hash_map<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        // Keep the paths set by `btree_map`, so that both can refine each other.
+        self.map_type
+            .matchers
+            .retain(|(_, map_type)| *map_type != MapType::HashMap);
+        for matcher in paths {
+            self.map_type
+                .insert(matcher.as_ref().to_string(), MapType::HashMap);
+        }
+        self
+    }
+
+    /// Set the Rust type generated for fields matched by [`hash_map`](Self::hash_map).
+    ///
+    /// Defaults to `std::collections::HashMap`. `no_std` crates can use a map such as
+    /// `hashbrown::HashMap` instead. The type is written as given, so it should be a path that
+    /// resolves from every generated module. It must take the key, value and hasher types as its
+    /// first three type parameters and provide `iter()` and `FromIterator`, like the standard
+    /// hash map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config
+    ///     .hash_map(&["."])
+    ///     .hash_map_path("hashbrown::HashMap");
+    /// ```
+    pub fn hash_map_path<S>(&mut self, path: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.hash_map_path = Some(path.into());
+        self
+    }
+
     /// Configure the code generator to generate Rust [`bytes::Bytes`](prost::bytes::Bytes) fields for Protobuf
     /// [`bytes`][2] type fields.
     ///
+    /// The generated code refers to `::bytes::Bytes` by its full path, so the generated crate needs
+    /// the `bytes` crate and the `bytes` feature of `parity-scale-codec`, which implements
+    /// `Encode` and `Decode` for it. Use [`bytes_path`](Self::bytes_path) to generate a different
+    /// type instead.
+    ///
     /// # Arguments
     ///
     /// **`paths`** - paths to specific fields, messages, or packages which should use a Rust
@@ -169,6 +289,30 @@ impl Config {
         self
     }
 
+    /// Set the Rust type generated for fields matched by [`bytes`](Self::bytes).
+    ///
+    /// Defaults to `::bytes::Bytes`. The type is written as given, so it should be a path that
+    /// resolves from every generated module, such as a fully qualified path or one starting with
+    /// `crate::`. It must implement `Encode` and `Decode` along with the derived traits of the
+    /// messages using it, and `From<Vec<u8>>`, which initialises fields declaring a proto2
+    /// default value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config
+    ///     .bytes(&["."])
+    ///     .bytes_path("crate::types::SharedBytes");
+    /// ```
+    pub fn bytes_path<S>(&mut self, path: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.bytes_path = Some(path.into());
+        self
+    }
+
     /// Add additional attribute to matched fields.
     ///
     /// # Arguments
@@ -219,6 +363,10 @@ impl Config {
     /// For things like serde it might be needed to combine with [field
     /// attributes](Self::field_attribute).
     ///
+    /// Messages and enums implement `Default`: it is derived when every member defaults to the
+    /// `Default` of its type, and otherwise implemented to apply the declared default values.
+    /// Neither is emitted for types whose attributes derive `Default`.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -360,6 +508,529 @@ impl Config {
         self
     }
 
+    /// Configure the code generator to use the generated Rust enum type for Protobuf enum fields
+    /// instead of a raw `i32`.
+    ///
+    /// By default enum fields are generated as `i32`, matching `prost`. That encodes every value
+    /// as a 4-byte integer and accepts any number when decoding. Matched fields use the enum type
+    /// itself, so SCALE writes a single byte variant index and decoding rejects unknown values.
+    /// This applies to singular, optional, repeated, map value and oneof enum fields.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - paths to specific fields, messages, or packages which should use the Rust
+    /// enum type. For details about matching fields see [`btree_map`](Self::btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Match a specific field in a message type.
+    /// config.typed_enums(&[".my_messages.MyMessageType.my_enum_field"]);
+    ///
+    /// // Match all enum fields.
+    /// config.typed_enums(&["."]);
+    /// ```
+    pub fn typed_enums<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.typed_enums.clear();
+        for matcher in paths {
+            self.typed_enums.insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Configure the code generator to use SCALE compact encoding for matched integer fields.
+    ///
+    /// Compact encoding writes small values in fewer bytes than the fixed-width default. Singular
+    /// fields get a `#[codec(compact)]` attribute, while optional and repeated fields and map
+    /// values are wrapped in `Compact<T>`. Only unsigned integer fields are affected, since SCALE
+    /// has no compact encoding for signed integers.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - paths to specific fields, messages, or packages which should use compact
+    /// encoding. For details about matching fields see [`btree_map`](Self::btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Match a specific field in a message type.
+    /// config.compact(&[".my_messages.MyMessageType.amount"]);
+    ///
+    /// // Match all fields named 'id'.
+    /// config.compact(&["id"]);
+    /// ```
+    pub fn compact<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.compact.clear();
+        for matcher in paths {
+            self.compact.insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Configures the derives emitted for matched messages, enums and oneofs.
+    ///
+    /// The list replaces the default `#[derive(Encode, Decode, Debug)]` entirely, so it should
+    /// normally include the codec derives. [`STANDARD_DERIVES`](crate::STANDARD_DERIVES) adds the
+    /// common standard library traits. `Eq` and `Hash` are only emitted for types whose fields
+    /// support them, for example not for messages that transitively contain `float` or `double`
+    /// fields. A derive is also left out when a message, enum or oneof that the fields refer to
+    /// is not configured with it, since the field would not implement the trait. `Default` is
+    /// left out, since messages and enums derive or implement it depending on their defaults.
+    ///
+    /// Types mapped with [`extern_path`](Self::extern_path) are not checked: they must implement
+    /// the derives of the types referring to them, apart from `Eq` and `Hash`, which are left out
+    /// for fields of extern message types.
+    ///
+    /// Calling this multiple times adds to the configuration; the most specific matching path
+    /// determines the derives of a type.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - a path matching any number of types. It works the same way as in
+    /// [`btree_map`](Self::btree_map), just with the field name omitted.
+    ///
+    /// **`derives`** - the traits to derive, as they should appear in the `#[derive]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Derive the standard traits everywhere.
+    /// config.derives(&["."], ppsc_build::STANDARD_DERIVES);
+    ///
+    /// // Only the codec derives and `Clone` for one message.
+    /// config.derives(&[".my_messages.Large"], &["Encode", "Decode", "Clone"]);
+    /// ```
+    pub fn derives<I, S, D, T>(&mut self, paths: I, derives: D) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        D: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let derives: Vec<String> = derives
+            .into_iter()
+            .map(|derive| derive.as_ref().to_string())
+            .collect();
+        for matcher in paths {
+            self.derives
+                .insert(matcher.as_ref().to_string(), derives.clone());
+        }
+        self
+    }
+
+    /// Configures whether generated types derive `scale_info::TypeInfo`.
+    ///
+    /// When enabled, every generated message, enum and oneof derives `TypeInfo` in addition to
+    /// its configured [`derives`](Self::derives), and the generated files import
+    /// `scale_info::TypeInfo`. The Protobuf comments emitted as doc comments are captured into
+    /// the type metadata with `#[scale_info(capture_docs = "always")]`.
+    ///
+    /// The generated code depends on the `scale-info` crate with its `derive` feature. Types
+    /// referenced through [`extern_path`](Self::extern_path) or a
+    /// [`FloatStrategy::Wrapper`](crate::FloatStrategy::Wrapper) must implement `TypeInfo`
+    /// themselves.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config.type_info(true);
+    /// ```
+    pub fn type_info(&mut self, enabled: bool) -> &mut Self {
+        self.type_info = enabled;
+        self
+    }
+
+    /// Bound the length of matched `string`, `bytes`, `repeated` and `map` fields.
+    ///
+    /// Matched fields use the bounded collection types of the `bounded-collections` crate, whose
+    /// decoding fails when the bound is exceeded: `string` and `bytes` fields become
+    /// `BoundedVec<u8, ConstU32<N>>`, repeated fields `BoundedVec<T, ConstU32<N>>` and map fields
+    /// `BoundedBTreeMap<K, V, ConstU32<N>>`. For repeated and map fields the bound is the number
+    /// of elements.
+    ///
+    /// Since `bounded-collections` has no bounded string type, a bounded `string` field holds the
+    /// UTF-8 encoding of the text, its bound is a number of bytes, and decoding does not check
+    /// that the bytes are valid UTF-8. A declared default longer than the bound is a code
+    /// generation error.
+    ///
+    /// Bounded fields are required for types deriving `MaxEncodedLen`, see
+    /// [`max_encoded_len`](Self::max_encoded_len).
+    ///
+    /// # Arguments
+    ///
+    /// **`path`** - a path matching any number of fields. For details about matching fields see
+    /// [`btree_map`](Self::btree_map).
+    ///
+    /// **`max_len`** - the maximum length of the matched fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config
+    ///     .max_len(".my_messages.Account.name", 64)
+    ///     .max_len(".my_messages.Account.keys", 16);
+    /// ```
+    pub fn max_len<P>(&mut self, path: P, max_len: u32) -> &mut Self
+    where
+        P: AsRef<str>,
+    {
+        self.max_len.insert(path.as_ref().to_string(), max_len);
+        self
+    }
+
+    /// Generate fixed-size byte arrays for matched `bytes` fields.
+    ///
+    /// Matched fields are generated as `[u8; N]` instead of a byte vector. SCALE encodes arrays
+    /// without a length prefix, and decoding reads exactly `N` bytes, which suits hashes, public
+    /// keys and signatures. Repeated fields become `Vec<[u8; N]>`, and `bytes` members of a oneof
+    /// or map values are matched through the field path like any other field. Fields of other
+    /// types are not affected.
+    ///
+    /// A fixed-size field has a bounded encoded length, so it needs no [`max_len`](Self::max_len)
+    /// for [`max_encoded_len`](Self::max_encoded_len); a `max_len` on a repeated fixed-size field
+    /// bounds the number of elements.
+    ///
+    /// # Arguments
+    ///
+    /// **`path`** - a path matching any number of fields. For details about matching fields see
+    /// [`btree_map`](Self::btree_map).
+    ///
+    /// **`len`** - the number of bytes of the matched fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config
+    ///     .fixed_bytes(".my_messages.Block.parent_hash", 32)
+    ///     .fixed_bytes(".my_messages.Block.signatures", 64);
+    /// ```
+    pub fn fixed_bytes<P>(&mut self, path: P, len: usize) -> &mut Self
+    where
+        P: AsRef<str>,
+    {
+        self.fixed_bytes.insert(path.as_ref().to_string(), len);
+        self
+    }
+
+    /// Configure the code generator to derive `MaxEncodedLen` for matched types.
+    ///
+    /// Every field of a matched message or oneof must have a bounded encoded length: `string`,
+    /// `bytes`, `repeated` and `map` fields need a [`max_len`](Self::max_len), and referenced
+    /// messages must derive `MaxEncodedLen` as well. Code generation fails with an error listing
+    /// every field that breaks this rule. Types referenced through
+    /// [`extern_path`](Self::extern_path) are assumed to implement `MaxEncodedLen`.
+    ///
+    /// The generated code requires the `max-encoded-len` feature of `parity-scale-codec`.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - paths to specific messages, enums or packages whose types should derive
+    /// `MaxEncodedLen`. It works the same way as in [`btree_map`](Self::btree_map), just with the
+    /// field name omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Every type in the `storage` package goes into runtime storage.
+    /// config.max_encoded_len(&[".storage"]);
+    /// ```
+    pub fn max_encoded_len<I, S>(&mut self, paths: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.max_encoded_len.clear();
+        for matcher in paths {
+            self.max_encoded_len
+                .insert(matcher.as_ref().to_string(), ());
+        }
+        self
+    }
+
+    /// Configures how integer fields are encoded by default.
+    ///
+    /// The default, [`EncodingProfile::FixedWidth`], encodes every integer at its fixed width.
+    /// With [`EncodingProfile::ProtoTypes`] the Protobuf varint types use SCALE compact encoding
+    /// and the `fixed*` types stay fixed width, so the intent of the `.proto` carries over without
+    /// per-field configuration. Fields matched by [`compact`](Self::compact) are always compact.
+    ///
+    /// Map keys are always encoded at their fixed width.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ppsc_build::{EncodingProfile, SignedVarint};
+    /// # let mut config = ppsc_build::Config::new();
+    /// config
+    ///     .encoding_profile(EncodingProfile::ProtoTypes)
+    ///     .signed_varint(SignedVarint::ZigZag);
+    /// ```
+    pub fn encoding_profile(&mut self, profile: EncodingProfile) -> &mut Self {
+        self.encoding_profile = profile;
+        self
+    }
+
+    /// Configures how signed varint fields (`int32`, `int64`, `sint32` and `sint64`) are encoded
+    /// under [`EncodingProfile::ProtoTypes`].
+    ///
+    /// SCALE compact encoding only supports unsigned integers. The default,
+    /// [`SignedVarint::ZigZag`], generates `ZigZagI32` and `ZigZagI64` wrapper types in each
+    /// module that needs them.
+    pub fn signed_varint(&mut self, strategy: SignedVarint) -> &mut Self {
+        self.signed_varint = strategy;
+        self
+    }
+
+    /// Configures how `float` and `double` fields are generated.
+    ///
+    /// `parity-scale-codec` does not implement `Encode` or `Decode` for `f32` and `f64`. The
+    /// default, [`FloatStrategy::BitCast`], generates `F32Bits` and `F64Bits` newtypes holding the
+    /// bit pattern of the value in each module that needs them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ppsc_build::FloatStrategy;
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Use our own wrapper types.
+    /// config.float_strategy(FloatStrategy::Wrapper {
+    ///     f32: "::my_codec::Float32".to_string(),
+    ///     f64: "::my_codec::Float64".to_string(),
+    /// });
+    ///
+    /// // Refuse schemas with floating point fields.
+    /// config.float_strategy(FloatStrategy::Reject);
+    /// ```
+    pub fn float_strategy(&mut self, strategy: FloatStrategy) -> &mut Self {
+        self.float_strategy = strategy;
+        self
+    }
+
+    /// Configures whether the generated code relies on `alloc` or `std`.
+    ///
+    /// The default, [`NoStdMode::Alloc`], generates code for `no_std` crates: it uses `alloc`
+    /// paths and every generated file starts with `extern crate alloc;`. When an
+    /// [`include_file`](Self::include_file) is configured, the include file declares `alloc` once
+    /// instead, so it should be included at the crate root, unless
+    /// [`extern_crate_alloc`](Self::extern_crate_alloc) is disabled. [`NoStdMode::Std`] uses
+    /// `std` paths and declares nothing, which suits generated code included anywhere in a `std`
+    /// crate.
+    ///
+    /// Some derives need `std`: the bounded collections used for [`max_len`](Self::max_len)
+    /// only implement `Hash` with the `std` feature of `bounded-collections`. These derives are
+    /// left out under `NoStdMode::Alloc`, emitted under `NoStdMode::Std`, and gated on a feature
+    /// of the including crate under [`NoStdMode::Feature`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ppsc_build::NoStdMode;
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Emit `#[cfg_attr(feature = "std", derive(Hash))]` where `Hash` needs `std`.
+    /// config.no_std_mode(NoStdMode::Feature("std".to_string()));
+    /// ```
+    pub fn no_std_mode(&mut self, mode: NoStdMode) -> &mut Self {
+        self.no_std_mode = mode;
+        self
+    }
+
+    /// Configures whether the generated code declares `extern crate alloc;` when it uses `alloc`
+    /// paths.
+    ///
+    /// Enabled by default. Disable it when the including crate declares `alloc` itself, such as
+    /// when the [`include_file`](Self::include_file) is included at a crate root that already
+    /// has `extern crate alloc;`, which would otherwise fail to compile.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config.extern_crate_alloc(false);
+    /// ```
+    pub fn extern_crate_alloc(&mut self, enabled: bool) -> &mut Self {
+        self.extern_crate_alloc = enabled;
+        self
+    }
+
+    /// Set the path through which the generated code refers to `parity-scale-codec`.
+    ///
+    /// Defaults to `parity_scale_codec`. Crates that depend on it under another name, like
+    /// `codec = { package = "parity-scale-codec", .. }` in Substrate, set the name here. The
+    /// path is used for the `Encode` and `Decode` imports and every other codec path, and is
+    /// passed to the codec derives with `#[codec(crate = ..)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config.codec_crate("::codec");
+    /// ```
+    pub fn codec_crate<S>(&mut self, path: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.codec_crate = Some(path.into());
+        self
+    }
+
+    /// Set the path through which the generated code refers to `scale-info`.
+    ///
+    /// Defaults to `scale_info`. Like [`codec_crate`](Self::codec_crate), the path is used for
+    /// the `TypeInfo` import and the generated support types, and is passed to the `TypeInfo`
+    /// derive with `#[scale_info(crate = ..)]`. Only used with [`type_info`](Self::type_info).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config.type_info(true).scale_info_crate("::scale_info_renamed");
+    /// ```
+    pub fn scale_info_crate<S>(&mut self, path: S) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.scale_info_crate = Some(path.into());
+        self
+    }
+
+    /// The path of the `parity-scale-codec` crate in generated code.
+    pub(crate) fn codec_crate_path(&self) -> &str {
+        self.codec_crate.as_deref().unwrap_or("parity_scale_codec")
+    }
+
+    /// The path of the `scale-info` crate in generated code.
+    pub(crate) fn scale_info_crate_path(&self) -> &str {
+        self.scale_info_crate.as_deref().unwrap_or("scale_info")
+    }
+
+    /// Configure how SCALE variant indices are assigned to the variants of matched enums.
+    ///
+    /// Every generated enum variant carries an explicit `#[codec(index = N)]` attribute. By
+    /// default the index is the Protobuf value number, which fails the build for enums with
+    /// values outside `0..=255`. Such enums can instead be numbered in declaration order, or have
+    /// individual variants pinned with [`codec_index`](Self::codec_index).
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - a path matching any number of enums. It works the same way as in
+    /// [`btree_map`](Self::btree_map), just with the field name omitted.
+    ///
+    /// **`index`** - the index assignment to use for the matched enums.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ppsc_build::EnumIndex;
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Number the variants of every enum in the `legacy` package in declaration order.
+    /// config.enum_index(&[".legacy"], EnumIndex::DeclarationOrder);
+    /// ```
+    pub fn enum_index<I, S>(&mut self, paths: I, index: EnumIndex) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for matcher in paths {
+            self.enum_index.insert(matcher.as_ref().to_string(), index);
+        }
+        self
+    }
+
+    /// Pin the SCALE variant index of a matched enum variant.
+    ///
+    /// This takes precedence over the assignment configured with
+    /// [`enum_index`](Self::enum_index). Two variants of the same enum ending up with the same
+    /// index is reported as an error.
+    ///
+    /// # Arguments
+    ///
+    /// **`path`** - a path matching enum variants, written as the enum path followed by the
+    /// Protobuf value name. For details about matching see [`btree_map`](Self::btree_map).
+    ///
+    /// **`index`** - the SCALE variant index for the matched variants.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // `STATUS_UNKNOWN = -1` cannot be used as a variant index.
+    /// config.codec_index(".my_messages.Status.STATUS_UNKNOWN", 255);
+    /// ```
+    pub fn codec_index<P>(&mut self, path: P, index: u8) -> &mut Self
+    where
+        P: AsRef<str>,
+    {
+        self.codec_index.insert(path.as_ref().to_string(), index);
+        self
+    }
+
+    /// Configure how SCALE variant indices are assigned to the variants of matched oneofs.
+    ///
+    /// By default oneof variants are indexed in declaration order, so reordering the fields of a
+    /// oneof in the `.proto` silently changes the encoding. With [`OneofIndex::FieldNumber`]
+    /// every variant is tagged with `#[codec(index = N)]`, where `N` is the Protobuf field number.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - a path matching any number of oneofs, written as the message path followed
+    /// by the oneof name. It works the same way as in [`btree_map`](Self::btree_map).
+    ///
+    /// **`index`** - the index assignment to use for the matched oneofs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ppsc_build::OneofIndex;
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Use field numbers for every oneof.
+    /// config.oneof_index(&["."], OneofIndex::FieldNumber);
+    /// ```
+    pub fn oneof_index<I, S>(&mut self, paths: I, index: OneofIndex) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for matcher in paths {
+            self.oneof_index.insert(matcher.as_ref().to_string(), index);
+        }
+        self
+    }
+
+    /// Configures the order in which message fields are laid out in generated structs.
+    ///
+    /// SCALE encodes struct fields positionally, so this order is the wire layout of every
+    /// message. The default, [`FieldOrder::Legacy`], places all oneofs after the regular fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ppsc_build::FieldOrder;
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Lay out fields by ascending field number.
+    /// config.field_order(FieldOrder::FieldNumber);
+    /// ```
+    pub fn field_order(&mut self, order: FieldOrder) -> &mut Self {
+        self.field_order = order;
+        self
+    }
+
     /// Configures the code generator to use the provided service generator.
     pub fn service_generator(&mut self, service_generator: Box<dyn ServiceGenerator>) -> &mut Self {
         self.service_generator = Some(service_generator);
@@ -402,7 +1073,30 @@ impl Config {
         self
     }
 
-    /// Skips generating `impl Debug` for types
+    /// Configure the code generator to redact fields from the `Debug` output of generated types.
+    ///
+    /// Types derive `Debug` by default. A message or oneof with matched fields gets a manual
+    /// `impl Debug` instead, which prints the type name and all other fields as usual but shows
+    /// `<redacted>` in place of the matched values. Use it to keep secrets out of logs.
+    ///
+    /// Types whose [`derives`](Self::derives) do not include `Debug` get no implementation.
+    ///
+    /// # Arguments
+    ///
+    /// **`paths`** - paths to specific fields, oneofs or messages whose values should be
+    /// redacted. Matching a message redacts all of its fields. For details about matching fields
+    /// see [`btree_map`](Self::btree_map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// // Redact a specific field.
+    /// config.skip_debug(&[".my_messages.Account.private_key"]);
+    ///
+    /// // Redact every field named `password`.
+    /// config.skip_debug(&["password"]);
+    /// ```
     pub fn skip_debug<I, S>(&mut self, paths: I) -> &mut Self
     where
         I: IntoIterator<Item = S>,
@@ -550,6 +1244,31 @@ impl Config {
         self
     }
 
+    /// Configures whether [`compile_protos`](Self::compile_protos) tells Cargo to rerun the
+    /// build script when its inputs change.
+    ///
+    /// When enabled, a `cargo:rerun-if-changed` line is printed for every `.proto` file read,
+    /// including the transitively imported ones, for every include directory, so that adding a
+    /// `.proto` file to it is noticed, and for the configuration file the `Config` was loaded
+    /// from. Cargo then reruns the build script when they are edited, and only then. An include
+    /// directory containing the output directory or `CARGO_TARGET_DIR`, such as the crate root,
+    /// is not watched, since the build itself changes it and would rerun the build script every
+    /// time. Disable it when generating code outside of a build script, where the lines would
+    /// only be noise.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # let mut config = ppsc_build::Config::new();
+    /// config.emit_rerun_if_changed(false);
+    /// ```
+    pub fn emit_rerun_if_changed(&mut self, enabled: bool) -> &mut Self {
+        self.emit_rerun_if_changed = enabled;
+        self
+    }
+
     /// Configures what filename protobufs with no package definition are written to.
     /// The filename will be appended with the `.rs` extension.
     pub fn default_package_filename<S>(&mut self, filename: S) -> &mut Self
@@ -629,6 +1348,10 @@ impl Config {
     /// This method is like `compile_protos` function except it does not invoke `protoc`
     /// and instead requires the user to supply a [`FileDescriptorSet`].
     ///
+    /// [`FileDescriptorSet`] does not retain the edition and the features of Protobuf Editions
+    /// files, so Editions files fail with an [`InvalidSyntax`](crate::Error::InvalidSyntax)
+    /// error. Use [`compile_encoded_fds`](Self::compile_encoded_fds) to compile them.
+    ///
     /// # Example `build.rs`
     ///
     /// ```rust,no_run
@@ -642,6 +1365,54 @@ impl Config {
     /// }
     /// ```
     pub fn compile_fds(&mut self, fds: FileDescriptorSet) -> Result<()> {
+        self.compile_fds_with_features(fds, &DescriptorFeatures::default())
+    }
+
+    /// Compile an encoded [`FileDescriptorSet`], as written by `protoc --descriptor_set_out`,
+    /// into Rust files during a Cargo build.
+    ///
+    /// Unlike [`compile_fds`](Self::compile_fds), this resolves the `edition` and the
+    /// `features` of Protobuf Editions files, including the features inherited from enclosing
+    /// files and messages.
+    ///
+    /// # Example `build.rs`
+    ///
+    /// ```rust,no_run
+    /// fn main() -> std::io::Result<()> {
+    ///   let encoded = std::fs::read("descriptors.bin")?;
+    ///
+    ///   ppsc_build::Config::new()
+    ///     .compile_encoded_fds(&encoded)
+    /// }
+    /// ```
+    pub fn compile_encoded_fds(&mut self, encoded_fds: &[u8]) -> Result<()> {
+        let fds = FileDescriptorSet::decode(encoded_fds)
+            .and_then(|fds| Ok((fds, DescriptorFeatures::decode(encoded_fds)?)));
+        let (fds, features) = fds.map_err(|error| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid FileDescriptorSet: {}", error),
+            )
+        })?;
+        self.compile_fds_with_features(fds, &features)
+    }
+
+    fn compile_fds_with_features(
+        &mut self,
+        fds: FileDescriptorSet,
+        features: &DescriptorFeatures,
+    ) -> Result<()> {
+        let (out_dir, files) = self.output_files(fds, features)?;
+        write_output_files(&out_dir, &files)
+    }
+
+    /// Generates the output files for `fds`, returning the output directory and the file names
+    /// relative to it and contents, ending with the [`Manifest`].
+    fn output_files(
+        &mut self,
+        fds: FileDescriptorSet,
+        features: &DescriptorFeatures,
+    ) -> Result<(PathBuf, Vec<(String, String)>)> {
         let mut target_is_env = false;
         let target: PathBuf = self.out_dir.clone().map(Ok).unwrap_or_else(|| {
             env::var_os("OUT_DIR")
@@ -654,8 +1425,50 @@ impl Config {
                 })
         })?;
 
-        let requests = fds
-            .file
+        let mut files = self.generate_files(
+            fds.file,
+            &[],
+            features,
+            if target_is_env { None } else { Some(&target) },
+        )?;
+        // An include file configured as an absolute path within the output directory is listed
+        // in the manifest relative to it.
+        for (file_name, _) in &mut files {
+            let path = Path::new(file_name.as_str());
+            if path.is_absolute()
+                && let Ok(relative) = path.strip_prefix(&target)
+            {
+                *file_name = relative.to_string_lossy().into_owned();
+            }
+        }
+        let mut manifest = Manifest::from_files(&files);
+        // Stale files edited since they were generated are kept, and stay listed so that
+        // `check_protos` keeps reporting them.
+        manifest.add_files(
+            stale_files(&target, &files)?
+                .into_iter()
+                .filter_map(|(file, modified)| modified.then_some(file)),
+        );
+        files.push((Manifest::FILE_NAME.to_owned(), manifest.to_string()));
+        Ok((target, files))
+    }
+
+    /// Generates the output files for `files`, as file names relative to the output directory
+    /// and contents, including the include file when one is configured.
+    ///
+    /// `imports` are the files that `files` depend on without being generated themselves, which
+    /// the types of `files` are resolved against.
+    ///
+    /// `out_dir` is the output directory, or `None` when it is `OUT_DIR`, which the include file
+    /// then refers to the generated files through.
+    pub(crate) fn generate_files(
+        &mut self,
+        files: Vec<FileDescriptorProto>,
+        imports: &[FileDescriptorProto],
+        features: &DescriptorFeatures,
+        out_dir: Option<&PathBuf>,
+    ) -> Result<Vec<(String, String)>> {
+        let requests = files
             .into_iter()
             .map(|descriptor| {
                 (
@@ -675,32 +1488,32 @@ impl Config {
             })
             .collect::<HashMap<Module, String>>();
 
-        let modules = self.generate(requests)?;
+        let modules = self.generate_with_features(requests, imports, features)?;
+        let mut files = Vec::with_capacity(modules.len() + 1);
         for (module, content) in &modules {
             let file_name = file_names
                 .get(module)
                 .expect("every module should have a filename");
-            let output_path = target.join(file_name);
-
-            write_file_if_changed(&output_path, content.as_bytes())?;
+            files.push((file_name.clone(), content.clone()));
         }
+        files.sort();
 
         if let Some(ref include_file) = self.include_file {
-            let path = target.join(include_file);
-            trace!("Writing include file: {}", path.display());
+            trace!("Writing include file: {}", include_file.display());
             let mut buffer = Vec::new();
             self.write_line(&mut buffer, 0, "// This file is @generated by ppsc-build.")?;
-            self.write_includes(
-                modules.keys().collect(),
-                &mut buffer,
-                if target_is_env { None } else { Some(&target) },
-                &file_names,
-            )?;
-
-            write_file_if_changed(&path, &buffer)?;
+            if self.no_std_mode != NoStdMode::Std && self.extern_crate_alloc {
+                self.write_line(&mut buffer, 0, "extern crate alloc;")?;
+            }
+            self.write_includes(modules.keys().collect(), &mut buffer, out_dir, &file_names)?;
+
+            files.push((
+                include_file.to_string_lossy().into_owned(),
+                String::from_utf8_lossy(&buffer).into_owned(),
+            ));
         }
 
-        Ok(())
+        Ok(files)
     }
 
     /// Compile `.proto` files into Rust files during a Cargo build with additional code generator
@@ -712,6 +1525,11 @@ impl Config {
     ///
     /// The `protos` and `includes` arguments are ignored if `skip_protoc_run` is specified.
     ///
+    /// The `.proto` files are parsed with `protox`, which does not support Protobuf Editions
+    /// files: they fail to parse at their `edition` declaration. Compile them with `protoc
+    /// --descriptor_set_out` and pass the result to
+    /// [`compile_encoded_fds`](Self::compile_encoded_fds) instead.
+    ///
     /// # Example `build.rs`
     ///
     /// ```rust,no_run
@@ -728,14 +1546,166 @@ impl Config {
         protos: &[impl AsRef<Path>],
         includes: &[impl AsRef<Path>],
     ) -> Result<()> {
-        let file_descriptor_set = protox::compile(protos, includes).map_err(|error| {
-            Error::new(
-                ErrorKind::InvalidInput,
-                format!("Failed to compile FileDiscriptorSet: {}", error),
-            )
-        })?;
+        let file_descriptor_set = compile_with_protox(protos, includes)?;
+        if self.emit_rerun_if_changed {
+            for path in self.rerun_if_changed_paths(&file_descriptor_set, includes) {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+
+        let (out_dir, files) = self.protos_output_files(file_descriptor_set, includes)?;
+        write_output_files(&out_dir, &files)
+    }
+
+    /// Checks that the files [`compile_protos`](Self::compile_protos) would write are up to
+    /// date, without writing them.
+    ///
+    /// Returns the paths of the output files that are missing or differ from a fresh
+    /// generation, and of the previously generated files that are no longer generated, which
+    /// `compile_protos` removes unless they were modified since. It is empty when the output
+    /// directory is up to date. This is meant for
+    /// generated code checked into a repository, to detect in CI that it was not regenerated
+    /// after a change to the `.proto` files or the configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::io::Result;
+    /// fn main() -> Result<()> {
+    ///   let stale = ppsc_build::Config::new()
+    ///     .out_dir("src/generated")
+    ///     .check_protos(&["src/items.proto"], &["src"])?;
+    ///   for path in &stale {
+    ///     eprintln!("out of date: {}", path.display());
+    ///   }
+    ///   std::process::exit(if stale.is_empty() { 0 } else { 1 })
+    /// }
+    /// ```
+    pub fn check_protos(
+        &mut self,
+        protos: &[impl AsRef<Path>],
+        includes: &[impl AsRef<Path>],
+    ) -> Result<Vec<PathBuf>> {
+        let file_descriptor_set = compile_with_protox(protos, includes)?;
+        let (out_dir, files) = self.protos_output_files(file_descriptor_set, includes)?;
+
+        let mut stale: Vec<PathBuf> = files
+            .iter()
+            .map(|(file_name, content)| (out_dir.join(file_name), content))
+            .filter(|(path, content)| {
+                !fs::read(path).is_ok_and(|previous| previous == content.as_bytes())
+            })
+            .map(|(path, _)| path)
+            .collect();
+        stale.extend(
+            stale_files(&out_dir, &files)?
+                .into_iter()
+                .map(|(file, _)| out_dir.join(file.name)),
+        );
+        Ok(stale)
+    }
+
+    /// Compiles the `.proto` files of the configuration, as loaded with
+    /// [`from_file`](Self::from_file) or [`from_cargo_metadata`](Self::from_cargo_metadata).
+    ///
+    /// This is like [`compile_protos`](Self::compile_protos) with the `protos` and `includes`
+    /// options, the current directory being the include directory when there are none. The
+    /// output directory is created if it is configured and does not exist.
+    ///
+    /// # Example `build.rs`
+    ///
+    /// ```rust,no_run
+    /// fn main() -> std::io::Result<()> {
+    ///   ppsc_build::Config::from_cargo_metadata()?.compile()
+    /// }
+    /// ```
+    pub fn compile(&mut self) -> Result<()> {
+        let (protos, includes) = self.configured_protos()?;
+        if let Some(out_dir) = &self.out_dir {
+            fs::create_dir_all(out_dir)?;
+        }
+        self.compile_protos(&protos, &includes)
+    }
+
+    /// Checks the output of [`compile`](Self::compile) like
+    /// [`check_protos`](Self::check_protos), returning the paths of the output files that are
+    /// missing or out of date.
+    pub fn check(&mut self) -> Result<Vec<PathBuf>> {
+        let (protos, includes) = self.configured_protos()?;
+        self.check_protos(&protos, &includes)
+    }
 
-        self.compile_fds(file_descriptor_set)
+    fn configured_protos(&self) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        if self.protos.is_empty() {
+            return Err(crate::Error::InvalidOption {
+                key: String::from("protos"),
+                message: String::from("no `.proto` files to compile; set the `protos` option"),
+            }
+            .into());
+        }
+        let includes = if self.includes.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            self.includes.clone()
+        };
+        Ok((self.protos.clone(), includes))
+    }
+
+    /// Returns the files a build script compiling `fds` depends on: the configuration file, the
+    /// include directories that do not contain the output directory or `CARGO_TARGET_DIR`, and
+    /// the `.proto` files found in the include directories, which excludes the well-known types
+    /// built into `protox`.
+    pub(crate) fn rerun_if_changed_paths(
+        &self,
+        fds: &FileDescriptorSet,
+        includes: &[impl AsRef<Path>],
+    ) -> Vec<PathBuf> {
+        let absolute = |path: &Path| fs::canonicalize(path).or_else(|_| path::absolute(path));
+        let build_dirs: Vec<PathBuf> = self
+            .out_dir
+            .clone()
+            .or_else(|| env::var_os("OUT_DIR").map(PathBuf::from))
+            .into_iter()
+            .chain(env::var_os("CARGO_TARGET_DIR").map(PathBuf::from))
+            .filter_map(|dir| absolute(&dir).ok())
+            .collect();
+
+        let mut paths: Vec<PathBuf> = self.config_file.iter().cloned().collect();
+        paths.extend(
+            includes
+                .iter()
+                .map(AsRef::as_ref)
+                .filter(|include| {
+                    absolute(include).is_ok_and(|include| {
+                        !build_dirs.iter().any(|dir| dir.starts_with(&include))
+                    })
+                })
+                .map(Path::to_owned),
+        );
+        for file in &fds.file {
+            let path = includes
+                .iter()
+                .map(|include| include.as_ref().join(file.name()))
+                .find(|path| path.is_file());
+            paths.extend(path);
+        }
+        paths
+    }
+
+    /// Generates the output files of `.proto` files compiled from `includes`, quoting the
+    /// sources in errors.
+    fn protos_output_files(
+        &mut self,
+        file_descriptor_set: FileDescriptorSet,
+        includes: &[impl AsRef<Path>],
+    ) -> Result<(PathBuf, Vec<(String, String)>)> {
+        self.output_files(file_descriptor_set, &DescriptorFeatures::default())
+            .map_err(|mut error| {
+                if let Some(error) = crate::Error::from_io_error_mut(&mut error) {
+                    error.add_snippet(includes);
+                }
+                error
+            })
     }
 
     pub(crate) fn write_includes(
@@ -799,16 +1769,31 @@ impl Config {
     /// This is generally used when control over the output should not be managed by Prost,
     /// such as in a flow for a `protoc` code generating plugin. When compiling as part of a
     /// `build.rs` file, instead use [`Self::compile_protos()`].
+    ///
+    /// Like [`compile_fds`](Self::compile_fds), this rejects Protobuf Editions files, whose
+    /// features the descriptors do not retain.
     pub fn generate(
         &mut self,
         requests: Vec<(Module, FileDescriptorProto)>,
+    ) -> Result<HashMap<Module, String>> {
+        self.generate_with_features(requests, &[], &DescriptorFeatures::default())
+    }
+
+    /// Like [`generate`](Self::generate), with the Editions features decoded separately from
+    /// the descriptors, and the `imports` of the requested files that are not generated.
+    pub(crate) fn generate_with_features(
+        &mut self,
+        requests: Vec<(Module, FileDescriptorProto)>,
+        imports: &[FileDescriptorProto],
+        features: &DescriptorFeatures,
     ) -> Result<HashMap<Module, String>> {
         let mut modules = HashMap::new();
         let mut packages = HashMap::new();
+        let mut support_types: HashMap<Module, BTreeSet<SupportType>> = HashMap::new();
 
-        let message_graph = MessageGraph::new(requests.iter().map(|x| &x.1));
-        let extern_paths = ExternPaths::new(&self.extern_paths)
-            .map_err(|error| Error::new(ErrorKind::InvalidInput, error))?;
+        let message_graph =
+            MessageGraph::new(requests.iter().map(|x| &x.1).chain(imports), features)?;
+        let extern_paths = ExternPaths::new(&self.extern_paths)?;
         let mut context = Context::new(self, message_graph, extern_paths);
 
         for (request_module, request_fd) in requests {
@@ -819,13 +1804,58 @@ impl Config {
             let buf = modules
                 .entry(request_module.clone())
                 .or_insert_with(String::new);
-            CodeGenerator::generate(&mut context, request_fd, buf);
+            CodeGenerator::generate(&mut context, request_fd, buf)?;
+            support_types
+                .entry(request_module.clone())
+                .or_default()
+                .append(&mut context.take_support_types());
             if buf.is_empty() {
                 // Did not generate any code, remove from list to avoid inclusion in include file or output file list
                 modules.remove(&request_module);
             }
         }
 
+        // Rejected fields are reported together, located at the first of them.
+        let float_fields = context.take_float_fields();
+        if let Some(first) = float_fields.first() {
+            return Err(crate::Error::UnsupportedType {
+                location: first.clone(),
+                message: format!(
+                    "SCALE has no encoding for floating point fields: {}; use `Config::float_strategy` to choose one",
+                    float_fields
+                        .iter()
+                        .map(|field| format!("`{}`", field.path))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+            .into());
+        }
+
+        let unbounded_fields = context.take_unbounded_fields();
+        if let Some((first, _)) = unbounded_fields.first() {
+            return Err(crate::Error::UnsupportedType {
+                location: first.clone(),
+                message: format!(
+                    "cannot derive `MaxEncodedLen` for types with unbounded fields: {}; use `Config::max_len` to bound them",
+                    unbounded_fields
+                        .iter()
+                        .map(|(_, description)| description.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+            .into());
+        }
+
+        for (module, types) in support_types {
+            if let Some(buf) = modules.get_mut(&module) {
+                for ty in types {
+                    ty.append(buf, context.config());
+                }
+            }
+        }
+
         if let Some(service_generator) = context.service_generator_mut() {
             for (module, package) in packages {
                 let buf = modules.get_mut(&module).unwrap();
@@ -846,6 +1876,69 @@ impl Config {
     }
 }
 
+/// Compiles `.proto` files with `protox`, quoting the sources in errors.
+pub(crate) fn compile_with_protox(
+    protos: &[impl AsRef<Path>],
+    includes: &[impl AsRef<Path>],
+) -> Result<FileDescriptorSet> {
+    protox::compile(protos, includes).map_err(|error| {
+        let mut error = crate::Error::from_protox(&error);
+        error.add_snippet(includes);
+        Error::from(error)
+    })
+}
+
+/// Writes the output files to `out_dir`, then removes the previously generated files that are
+/// no longer generated.
+fn write_output_files(out_dir: &Path, files: &[(String, String)]) -> Result<()> {
+    let removed = removed_files(out_dir, files)?;
+    for (file_name, content) in files {
+        write_file_if_changed(&out_dir.join(file_name), content.as_bytes())?;
+    }
+    for path in removed {
+        trace!("removing: {}", path.display());
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Returns the files listed in the manifest of `out_dir` that are not part of `files`, and
+/// are unmodified since they were generated.
+fn removed_files(out_dir: &Path, files: &[(String, String)]) -> Result<Vec<PathBuf>> {
+    Ok(stale_files(out_dir, files)?
+        .into_iter()
+        .filter(|(_, modified)| !modified)
+        .map(|(file, _)| out_dir.join(file.name))
+        .collect())
+}
+
+/// Returns the files listed in the manifest of `out_dir` that are not part of `files` and still
+/// exist, with whether they were modified since they were generated.
+///
+/// There are none when `out_dir` has no manifest, but a manifest that cannot be read fails.
+fn stale_files(out_dir: &Path, files: &[(String, String)]) -> Result<Vec<(ManifestFile, bool)>> {
+    let manifest = match Manifest::read(out_dir) {
+        Ok(manifest) => manifest,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            let path = out_dir.join(Manifest::FILE_NAME);
+            return Err(Error::new(
+                error.kind(),
+                format!("{}: {}", path.display(), error),
+            ));
+        }
+    };
+    Ok(manifest
+        .files()
+        .iter()
+        .filter(|file| !files.iter().any(|(file_name, _)| *file_name == file.name))
+        .filter_map(|file| {
+            let content = fs::read(out_dir.join(&file.name)).ok()?;
+            Some((file.clone(), Manifest::content_hash(&content) != file.hash))
+        })
+        .collect())
+}
+
 /// Write a slice as the entire contents of a file.
 ///
 /// This function will create a file if it does not exist,
@@ -871,12 +1964,32 @@ impl default::Default for Config {
         Config {
             service_generator: None,
             map_type: PathMap::default(),
+            hash_map_path: None,
             bytes_type: PathMap::default(),
+            bytes_path: None,
             type_attributes: PathMap::default(),
             message_attributes: PathMap::default(),
             enum_attributes: PathMap::default(),
             field_attributes: PathMap::default(),
             boxed: PathMap::default(),
+            typed_enums: PathMap::default(),
+            compact: PathMap::default(),
+            derives: PathMap::default(),
+            type_info: false,
+            max_len: PathMap::default(),
+            fixed_bytes: PathMap::default(),
+            max_encoded_len: PathMap::default(),
+            encoding_profile: EncodingProfile::default(),
+            signed_varint: SignedVarint::default(),
+            float_strategy: FloatStrategy::default(),
+            no_std_mode: NoStdMode::default(),
+            extern_crate_alloc: true,
+            codec_crate: None,
+            scale_info_crate: None,
+            enum_index: PathMap::default(),
+            codec_index: PathMap::default(),
+            oneof_index: PathMap::default(),
+            field_order: FieldOrder::default(),
             strip_enum_prefix: true,
             out_dir: None,
             extern_paths: Vec::new(),
@@ -885,6 +1998,10 @@ impl default::Default for Config {
             disable_comments: PathMap::default(),
             skip_debug: PathMap::default(),
             include_file: None,
+            protos: Vec::new(),
+            includes: Vec::new(),
+            emit_rerun_if_changed: true,
+            config_file: None,
         }
     }
 }
@@ -894,9 +2011,29 @@ impl fmt::Debug for Config {
         fmt.debug_struct("Config")
             .field("service_generator", &self.service_generator.is_some())
             .field("map_type", &self.map_type)
+            .field("hash_map_path", &self.hash_map_path)
             .field("bytes_type", &self.bytes_type)
+            .field("bytes_path", &self.bytes_path)
             .field("type_attributes", &self.type_attributes)
             .field("field_attributes", &self.field_attributes)
+            .field("typed_enums", &self.typed_enums)
+            .field("compact", &self.compact)
+            .field("derives", &self.derives)
+            .field("type_info", &self.type_info)
+            .field("max_len", &self.max_len)
+            .field("fixed_bytes", &self.fixed_bytes)
+            .field("max_encoded_len", &self.max_encoded_len)
+            .field("encoding_profile", &self.encoding_profile)
+            .field("signed_varint", &self.signed_varint)
+            .field("float_strategy", &self.float_strategy)
+            .field("no_std_mode", &self.no_std_mode)
+            .field("extern_crate_alloc", &self.extern_crate_alloc)
+            .field("codec_crate", &self.codec_crate)
+            .field("scale_info_crate", &self.scale_info_crate)
+            .field("enum_index", &self.enum_index)
+            .field("codec_index", &self.codec_index)
+            .field("oneof_index", &self.oneof_index)
+            .field("field_order", &self.field_order)
             .field("strip_enum_prefix", &self.strip_enum_prefix)
             .field("out_dir", &self.out_dir)
             .field("extern_paths", &self.extern_paths)
@@ -904,6 +2041,11 @@ impl fmt::Debug for Config {
             .field("type_name_domains", &self.type_name_domains)
             .field("disable_comments", &self.disable_comments)
             .field("skip_debug", &self.skip_debug)
+            .field("include_file", &self.include_file)
+            .field("protos", &self.protos)
+            .field("includes", &self.includes)
+            .field("emit_rerun_if_changed", &self.emit_rerun_if_changed)
+            .field("config_file", &self.config_file)
             .finish()
     }
 }
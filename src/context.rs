@@ -1,13 +1,21 @@
 use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 
 use prost_types::{
-    FieldDescriptorProto,
+    EnumDescriptorProto, FieldDescriptorProto,
     field_descriptor_proto::{Label, Type},
 };
 
+use crate::code_generator::SupportType;
+use crate::derives::{DEFAULT_DERIVES, DeriveSupport, Derives};
+use crate::error::Location;
 use crate::extern_paths::ExternPaths;
+use crate::features::{EnumType, FieldPresence};
 use crate::message_graph::MessageGraph;
-use crate::{BytesType, Config, MapType, ServiceGenerator};
+use crate::{
+    BytesType, Config, EncodingProfile, EnumIndex, FloatStrategy, MapType, NoStdMode, OneofIndex,
+    ServiceGenerator,
+};
 
 /// The context providing all the global information needed to generate code.
 /// It also provides a more disciplined access to Config
@@ -19,6 +27,9 @@ pub struct Context<'a> {
     config: &'a mut Config,
     message_graph: MessageGraph,
     extern_paths: ExternPaths,
+    support_types: BTreeSet<SupportType>,
+    float_fields: Vec<Location>,
+    unbounded_fields: Vec<(Location, String)>,
 }
 
 impl<'a> Context<'a> {
@@ -31,6 +42,9 @@ impl<'a> Context<'a> {
             config,
             message_graph,
             extern_paths,
+            support_types: BTreeSet::new(),
+            float_fields: Vec::new(),
+            unbounded_fields: Vec::new(),
         }
     }
 
@@ -46,6 +60,39 @@ impl<'a> Context<'a> {
         self.extern_paths.resolve_ident(pb_ident)
     }
 
+    /// Returns whether a singular field of the named message is generated as an `Option`.
+    ///
+    /// Message fields and fields with explicit presence are, unless they are required.
+    pub(crate) fn is_optional(&self, fq_message_name: &str, field: &FieldDescriptorProto) -> bool {
+        if field.proto3_optional.unwrap_or(false) {
+            return true;
+        }
+        if field.label() != Label::Optional {
+            return false;
+        }
+        let presence = self
+            .message_graph
+            .field_features(fq_message_name, field.name())
+            .map_or(FieldPresence::Explicit, |features| features.field_presence);
+        match presence {
+            FieldPresence::Explicit => true,
+            FieldPresence::Implicit => matches!(field.r#type(), Type::Message | Type::Group),
+            FieldPresence::LegacyRequired => false,
+        }
+    }
+
+    /// Returns whether the named enum is open or closed.
+    pub(crate) fn enum_type(&self, fq_enum_name: &str) -> EnumType {
+        self.message_graph
+            .enum_features(fq_enum_name)
+            .map_or(EnumType::Open, |features| features.enum_type)
+    }
+
+    /// Returns the descriptor of the named enum, if it is part of the generated files.
+    pub(crate) fn enum_descriptor(&self, fq_enum_name: &str) -> Option<&EnumDescriptorProto> {
+        self.message_graph.get_enum(fq_enum_name)
+    }
+
     /// Returns an iterator over the additional attributes configured
     /// for the named type.
     pub fn type_attributes(&self, fq_type_name: &str) -> impl Iterator<Item = &str> {
@@ -95,6 +142,17 @@ impl<'a> Context<'a> {
             .unwrap_or_default()
     }
 
+    /// Returns the Rust type generated for the named `bytes` field.
+    pub(crate) fn bytes_rust_type(&self, fq_message_name: &str, field_name: &str) -> String {
+        match (
+            self.bytes_type(fq_message_name, field_name),
+            &self.config.bytes_path,
+        ) {
+            (BytesType::Bytes, Some(path)) => path.clone(),
+            (bytes_type, _) => bytes_type.rust_type(self.alloc_crate()),
+        }
+    }
+
     /// Returns the map type configured for the named message field.
     pub(crate) fn map_type(&self, fq_message_name: &str, field_name: &str) -> MapType {
         self.config
@@ -104,6 +162,358 @@ impl<'a> Context<'a> {
             .unwrap_or_default()
     }
 
+    /// Returns the Rust type generated for the named map field.
+    pub(crate) fn map_rust_type(&self, fq_message_name: &str, field_name: &str) -> String {
+        match (
+            self.map_type(fq_message_name, field_name),
+            &self.config.hash_map_path,
+        ) {
+            (MapType::HashMap, Some(path)) => path.clone(),
+            (map_type, _) => map_type.rust_type(self.alloc_crate()),
+        }
+    }
+
+    /// Returns the crate providing collections and boxes to the generated code.
+    pub(crate) fn alloc_crate(&self) -> &'static str {
+        self.config.no_std_mode.alloc_crate()
+    }
+
+    /// Returns whether the named enum field should use the generated enum type instead of `i32`.
+    pub fn should_type_enum_field(&self, fq_message_name: &str, field_name: &str) -> bool {
+        self.config
+            .typed_enums
+            .get_first_field(fq_message_name, field_name)
+            .is_some()
+    }
+
+    /// Returns whether this integer field should use SCALE compact encoding, either because it
+    /// is configured with `Config::compact` or because the encoding profile maps its Protobuf
+    /// type to compact.
+    ///
+    /// `field_name` is the name used to match the configuration, which for map values is the
+    /// name of the map field.
+    pub fn should_compact_field(
+        &self,
+        fq_message_name: &str,
+        field_name: &str,
+        field: &FieldDescriptorProto,
+    ) -> bool {
+        match field.r#type() {
+            Type::Uint32 | Type::Uint64 => {
+                self.config.encoding_profile == EncodingProfile::ProtoTypes
+                    || self.is_compact_configured(fq_message_name, field_name)
+            }
+            Type::Fixed32 | Type::Fixed64 => {
+                self.is_compact_configured(fq_message_name, field_name)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_compact_configured(&self, fq_message_name: &str, field_name: &str) -> bool {
+        self.config
+            .compact
+            .get_first_field(fq_message_name, field_name)
+            .is_some()
+    }
+
+    /// Returns whether `Config::compact` names this exact field, rather than matching it
+    /// through one of its enclosing messages or packages.
+    pub fn is_compact_field_named(&self, fq_message_name: &str, field_name: &str) -> bool {
+        let fq_field_name = format!("{}.{}", fq_message_name, field_name);
+        self.config
+            .compact
+            .matchers
+            .iter()
+            .any(|(path, ())| *path == fq_field_name)
+    }
+
+    /// Returns the derives to emit for the named message, enum or oneof type.
+    ///
+    /// `fields` are the fields making up the type, declared in `fq_message_name`. They are
+    /// checked transitively, and standard derives that some field cannot support are dropped,
+    /// as are derives that are not configured for every generated message, enum and oneof the
+    /// fields refer to, since their types would not implement them.
+    /// Derives that need `std` are placed according to the [`NoStdMode`]. `Default` is never
+    /// derived, since messages and enums get a generated implementation, unless their type
+    /// attributes derive it.
+    pub fn derives(
+        &self,
+        fq_type_name: &str,
+        fq_message_name: &str,
+        fields: &[FieldDescriptorProto],
+    ) -> Derives {
+        let mut visited = HashSet::new();
+        let support = self.fields_derive_support(fq_message_name, fields, &mut visited);
+        let mut derives = Derives::default();
+        for derive in self.configured_derives(fq_type_name) {
+            if derive == "Default" || !support.allows(derive) {
+                continue;
+            }
+            if visited
+                .iter()
+                .any(|referenced| !self.configured_derives(referenced).contains(&derive))
+            {
+                continue;
+            }
+            if !support.needs_std(derive) {
+                derives.derives.push(derive.to_owned());
+                continue;
+            }
+            match self.config.no_std_mode {
+                NoStdMode::Alloc => {}
+                NoStdMode::Std => derives.derives.push(derive.to_owned()),
+                NoStdMode::Feature(_) => derives.std_derives.push(derive.to_owned()),
+            }
+        }
+        if self.should_derive_max_encoded_len(fq_type_name) {
+            derives
+                .derives
+                .push(format!("{}::MaxEncodedLen", self.config.codec_crate_path()));
+        }
+        if self.config.type_info {
+            derives.derives.push(String::from("TypeInfo"));
+        }
+        derives
+    }
+
+    /// Returns the derives configured for the named type, before they are checked against its
+    /// fields.
+    fn configured_derives(&self, fq_type_name: &str) -> Vec<&str> {
+        match self.config.derives.get_first(fq_type_name) {
+            Some(derives) => derives.iter().map(String::as_str).collect(),
+            None => DEFAULT_DERIVES.to_vec(),
+        }
+    }
+
+    /// Returns whether the named field or oneof should be redacted from `Debug` output.
+    pub fn should_redact_debug(&self, fq_message_name: &str, field_name: &str) -> bool {
+        self.config
+            .skip_debug
+            .get_first_field(fq_message_name, field_name)
+            .is_some()
+    }
+
+    fn fields_derive_support(
+        &self,
+        fq_message_name: &str,
+        fields: &[FieldDescriptorProto],
+        visited: &mut HashSet<String>,
+    ) -> DeriveSupport {
+        fields.iter().fold(DeriveSupport::ALL, |support, field| {
+            support.and(self.field_derive_support(fq_message_name, field, visited))
+        })
+    }
+
+    fn field_derive_support(
+        &self,
+        fq_message_name: &str,
+        field: &FieldDescriptorProto,
+        visited: &mut HashSet<String>,
+    ) -> DeriveSupport {
+        // A oneof member is held in the generated oneof enum, which has derives of its own.
+        if let (Some(index), false) = (field.oneof_index, field.proto3_optional()) {
+            let oneof = self
+                .message_graph
+                .get_message(fq_message_name)
+                .and_then(|message| message.oneof_decl.get(index as usize));
+            if let Some(oneof) = oneof {
+                visited.insert(format!("{}.{}", fq_message_name, oneof.name()));
+            }
+        }
+        // The bounded collections only implement `Hash` with their `std` feature.
+        let bounded = (field.label() == Label::Repeated
+            || (matches!(field.r#type(), Type::String | Type::Bytes)
+                && self
+                    .fixed_bytes(fq_message_name, field.name(), field)
+                    .is_none()))
+            && self.max_len(fq_message_name, field.name()).is_some();
+        let support = if bounded {
+            DeriveSupport::STD_HASH
+        } else {
+            DeriveSupport::ALL
+        };
+        support.and(self.field_type_derive_support(fq_message_name, field, visited))
+    }
+
+    fn field_type_derive_support(
+        &self,
+        fq_message_name: &str,
+        field: &FieldDescriptorProto,
+        visited: &mut HashSet<String>,
+    ) -> DeriveSupport {
+        match field.r#type() {
+            Type::Float | Type::Double => self.float_derive_support(),
+            Type::Message | Type::Group => {
+                let Some(message) = self.message_graph.get_message(field.type_name()) else {
+                    // Extern types might not implement the traits.
+                    return DeriveSupport::NONE;
+                };
+                if self.extern_paths.resolve_ident(field.type_name()).is_some() {
+                    return DeriveSupport::NONE;
+                }
+                let is_map = message
+                    .options
+                    .as_ref()
+                    .and_then(|options| options.map_entry)
+                    .unwrap_or(false);
+                if is_map {
+                    let value = &message.field[1];
+                    let mut support = match self.map_type(fq_message_name, field.name()) {
+                        MapType::HashMap => DeriveSupport::NO_HASH,
+                        MapType::BTreeMap => DeriveSupport::ALL,
+                    };
+                    if value.r#type() == Type::Enum {
+                        self.visit_typed_enum(fq_message_name, field.name(), value, visited);
+                    }
+                    if value.r#type() == Type::Message {
+                        support =
+                            support.and(self.field_derive_support(fq_message_name, value, visited));
+                    } else if value.r#type() == Type::Float || value.r#type() == Type::Double {
+                        support = support.and(self.float_derive_support());
+                    } else if self.should_compact_field(fq_message_name, field.name(), value) {
+                        support = support.and(DeriveSupport::NO_HASH);
+                    }
+                    support
+                } else if visited.insert(field.type_name().to_string()) {
+                    self.fields_derive_support(field.type_name(), &message.field, visited)
+                } else {
+                    DeriveSupport::ALL
+                }
+            }
+            _ => {
+                if field.r#type() == Type::Enum {
+                    self.visit_typed_enum(fq_message_name, field.name(), field, visited);
+                }
+                // `Compact` does not implement `Hash`, so wrapped compact fields rule it out.
+                let wrapped =
+                    field.label() == Label::Repeated || self.is_optional(fq_message_name, field);
+                if wrapped && self.should_compact_field(fq_message_name, field.name(), field) {
+                    DeriveSupport::NO_HASH
+                } else {
+                    DeriveSupport::ALL
+                }
+            }
+        }
+    }
+
+    /// Records the generated enum type of an enum field, unless the field is a plain `i32` or
+    /// refers to an extern type.
+    fn visit_typed_enum(
+        &self,
+        fq_message_name: &str,
+        field_name: &str,
+        field: &FieldDescriptorProto,
+        visited: &mut HashSet<String>,
+    ) {
+        if self.should_type_enum_field(fq_message_name, field_name)
+            && self.message_graph.get_enum(field.type_name()).is_some()
+            && self.extern_paths.resolve_ident(field.type_name()).is_none()
+        {
+            visited.insert(field.type_name().to_owned());
+        }
+    }
+
+    /// The generated `F32Bits` and `F64Bits` types derive `Eq` and `Hash`, but user-supplied
+    /// wrapper types might not.
+    fn float_derive_support(&self) -> DeriveSupport {
+        match self.config.float_strategy {
+            FloatStrategy::BitCast => DeriveSupport::ALL,
+            FloatStrategy::Wrapper { .. } | FloatStrategy::Reject => DeriveSupport::NONE,
+        }
+    }
+
+    /// Records that the code being generated refers to a support type.
+    pub fn require_support_type(&mut self, support_type: SupportType) {
+        self.support_types.insert(support_type);
+    }
+
+    /// Returns the support types required since the last call, leaving the set empty.
+    pub fn take_support_types(&mut self) -> BTreeSet<SupportType> {
+        std::mem::take(&mut self.support_types)
+    }
+
+    /// Records a floating point field rejected by [`FloatStrategy::Reject`](crate::FloatStrategy::Reject).
+    pub fn reject_float_field(&mut self, location: Location) {
+        self.float_fields.push(location);
+    }
+
+    /// Returns all floating point fields rejected so far.
+    pub fn take_float_fields(&mut self) -> Vec<Location> {
+        std::mem::take(&mut self.float_fields)
+    }
+
+    /// Records a field that prevents its type from deriving `MaxEncodedLen`, along with the
+    /// reason.
+    pub fn reject_unbounded_field(&mut self, location: Location, description: String) {
+        self.unbounded_fields.push((location, description));
+    }
+
+    /// Returns all fields rejected as unbounded so far.
+    pub fn take_unbounded_fields(&mut self) -> Vec<(Location, String)> {
+        std::mem::take(&mut self.unbounded_fields)
+    }
+
+    /// Returns the maximum length configured for the named field.
+    pub fn max_len(&self, fq_message_name: &str, field_name: &str) -> Option<u32> {
+        self.config
+            .max_len
+            .get_first_field(fq_message_name, field_name)
+            .copied()
+    }
+
+    /// Returns the array length configured for the named field, if `field` is of type `bytes`.
+    ///
+    /// For map fields `field_name` names the map and `field` is its value entry.
+    pub fn fixed_bytes(
+        &self,
+        fq_message_name: &str,
+        field_name: &str,
+        field: &FieldDescriptorProto,
+    ) -> Option<usize> {
+        if field.r#type() != Type::Bytes {
+            return None;
+        }
+        self.config
+            .fixed_bytes
+            .get_first_field(fq_message_name, field_name)
+            .copied()
+    }
+
+    /// Returns whether the named type should derive `MaxEncodedLen`.
+    pub fn should_derive_max_encoded_len(&self, fq_type_name: &str) -> bool {
+        self.config
+            .max_encoded_len
+            .get_first(fq_type_name)
+            .is_some()
+    }
+
+    /// Returns the SCALE variant index assignment configured for the named enum.
+    pub fn enum_index(&self, fq_enum_name: &str) -> EnumIndex {
+        self.config
+            .enum_index
+            .get_first(fq_enum_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the SCALE variant index pinned for the named enum variant, if any.
+    pub fn codec_index(&self, fq_enum_name: &str, value_name: &str) -> Option<u8> {
+        self.config
+            .codec_index
+            .get_first_field(fq_enum_name, value_name)
+            .copied()
+    }
+
+    /// Returns the SCALE variant index assignment configured for the named oneof.
+    pub fn oneof_index(&self, fq_message_name: &str, oneof_name: &str) -> OneofIndex {
+        self.config
+            .oneof_index
+            .get_first_field(fq_message_name, oneof_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Returns whether the Rust type for this message field needs to be `Box<_>`.
     ///
     /// This can be explicitly configured with `Config::boxed`, or necessary
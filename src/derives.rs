@@ -0,0 +1,123 @@
+/// The derives emitted for generated types that no [`Config::derives`](crate::Config::derives)
+/// setting matches.
+pub(crate) const DEFAULT_DERIVES: &[&str] = &["Encode", "Decode", "Debug"];
+
+/// The SCALE codec derives together with the common standard library traits.
+///
+/// `Eq` and `Hash` are only emitted for types whose fields can implement them: types that
+/// transitively contain floating point fields or extern types get neither, and types containing
+/// `HashMap` fields or `Compact` wrappers do not get `Hash`. Types containing bounded collections
+/// get `Hash` as configured by [`NoStdMode`](crate::NoStdMode).
+pub const STANDARD_DERIVES: &[&str] = &[
+    "Encode",
+    "Decode",
+    "Debug",
+    "Clone",
+    "PartialEq",
+    "Eq",
+    "Hash",
+];
+
+/// Returns whether `derive` names a `parity-scale-codec` derive macro, which reads
+/// `#[codec(..)]` attributes.
+pub(crate) fn is_codec_derive(derive: &str) -> bool {
+    matches!(
+        derive.rsplit("::").next(),
+        Some("Encode" | "Decode" | "MaxEncodedLen")
+    )
+}
+
+/// Returns whether a type attribute, such as `#[derive(Clone, Default)]`, derives `Default`.
+pub(crate) fn attribute_derives_default(attribute: &str) -> bool {
+    attribute.match_indices("derive(").any(|(start, matched)| {
+        let list = &attribute[start + matched.len()..];
+        let list = list.split(')').next().unwrap_or(list);
+        list.split(',')
+            .any(|derive| derive.trim().rsplit("::").next() == Some("Default"))
+    })
+}
+
+/// The derives of a generated type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Derives {
+    /// Derives emitted unconditionally.
+    pub(crate) derives: Vec<String>,
+    /// Derives that need `std`, emitted behind the feature of
+    /// [`NoStdMode::Feature`](crate::NoStdMode::Feature).
+    pub(crate) std_derives: Vec<String>,
+}
+
+/// Which of the standard derives with field requirements a type can support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DeriveSupport {
+    pub(crate) eq: bool,
+    pub(crate) hash: bool,
+    /// Whether `Hash` relies on the `std` feature of a dependency.
+    pub(crate) hash_needs_std: bool,
+}
+
+impl DeriveSupport {
+    pub(crate) const ALL: DeriveSupport = DeriveSupport {
+        eq: true,
+        hash: true,
+        hash_needs_std: false,
+    };
+
+    pub(crate) const NONE: DeriveSupport = DeriveSupport {
+        eq: false,
+        hash: false,
+        hash_needs_std: false,
+    };
+
+    pub(crate) const NO_HASH: DeriveSupport = DeriveSupport {
+        eq: true,
+        hash: false,
+        hash_needs_std: false,
+    };
+
+    pub(crate) const STD_HASH: DeriveSupport = DeriveSupport {
+        eq: true,
+        hash: true,
+        hash_needs_std: true,
+    };
+
+    pub(crate) fn and(self, other: DeriveSupport) -> DeriveSupport {
+        DeriveSupport {
+            eq: self.eq && other.eq,
+            hash: self.hash && other.hash,
+            hash_needs_std: self.hash_needs_std || other.hash_needs_std,
+        }
+    }
+
+    /// Returns whether `derive` can only be emitted when `std` is available.
+    pub(crate) fn needs_std(self, derive: &str) -> bool {
+        derive == "Hash" && self.hash_needs_std
+    }
+
+    /// Returns whether `derive` can be emitted for a type with this support.
+    pub(crate) fn allows(self, derive: &str) -> bool {
+        match derive {
+            "Eq" => self.eq,
+            "Hash" => self.hash,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_derives_default() {
+        assert!(attribute_derives_default("#[derive(Default)]"));
+        assert!(attribute_derives_default(
+            "#[derive(Clone, ::core::default::Default)]"
+        ));
+        assert!(attribute_derives_default(
+            "#[cfg_attr(feature = \"std\", derive(Default))]"
+        ));
+        assert!(!attribute_derives_default("#[derive(Clone, DefaultValue)]"));
+        assert!(!attribute_derives_default("#[serde(default)]"));
+    }
+}
@@ -0,0 +1,92 @@
+/// How SCALE variant indices are assigned to the variants of a generated Protobuf enum.
+///
+/// SCALE encodes an enum as a single byte variant index, so every index must fit in `0..=255`.
+/// Individual variants can be pinned with [`Config::codec_index`](crate::Config::codec_index).
+#[non_exhaustive]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnumIndex {
+    /// Use the Protobuf enum value number. Values outside `0..=255` are rejected.
+    #[default]
+    ProtoNumber,
+    /// Number the variants in declaration order, starting at zero.
+    DeclarationOrder,
+}
+
+/// How SCALE variant indices are assigned to the variants of a generated oneof enum.
+#[non_exhaustive]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OneofIndex {
+    /// Leave the variants unannotated, so the index is the position of the field in the oneof.
+    /// Reordering the fields in the `.proto` changes the encoding.
+    #[default]
+    DeclarationOrder,
+    /// Use the Protobuf field number, which survives reordering. Field numbers above 255 are
+    /// rejected.
+    FieldNumber,
+}
+
+/// The order in which the fields of a generated message struct are laid out.
+///
+/// SCALE encodes struct fields positionally, so this order is the wire layout of the message.
+#[non_exhaustive]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldOrder {
+    /// Regular fields in declaration order, followed by all oneofs.
+    #[default]
+    Legacy,
+    /// Proto declaration order, with each oneof at the position of its first member.
+    Declaration,
+    /// Ascending field number, with each oneof at the position of its lowest numbered member.
+    FieldNumber,
+}
+
+/// How integer fields are encoded when no per-field [`Config::compact`](crate::Config::compact)
+/// setting applies.
+#[non_exhaustive]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodingProfile {
+    /// Encode every integer at its fixed width, regardless of its Protobuf type.
+    #[default]
+    FixedWidth,
+    /// Follow the Protobuf type: the varint types (`uint32`, `uint64`, `int32`, `int64`,
+    /// `sint32` and `sint64`) use SCALE compact encoding, while the `fixed*` and `sfixed*` types
+    /// keep their fixed width. Signed varint types are handled according to [`SignedVarint`].
+    ProtoTypes,
+}
+
+/// How signed varint fields are encoded under [`EncodingProfile::ProtoTypes`].
+///
+/// SCALE compact encoding is only defined for unsigned integers.
+#[non_exhaustive]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignedVarint {
+    /// Zigzag map the value onto an unsigned integer and encode that as compact, using the
+    /// generated `ZigZagI32` and `ZigZagI64` wrapper types.
+    #[default]
+    ZigZag,
+    /// Keep the fixed width encoding.
+    FixedWidth,
+    /// Fail code generation if a signed varint field is encountered.
+    Reject,
+}
+
+/// How `float` and `double` fields are represented, since SCALE has no floating point encoding.
+#[non_exhaustive]
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub enum FloatStrategy {
+    /// Store the IEEE 754 bit pattern in the generated `F32Bits` and `F64Bits` newtypes, which
+    /// encode as `u32` and `u64`.
+    #[default]
+    BitCast,
+    /// Use user-supplied types, given as Rust paths in the same form as
+    /// [`Config::extern_path`](crate::Config::extern_path) targets. The types must implement
+    /// `Encode` and `Decode`.
+    Wrapper {
+        /// The Rust type used for `float` fields.
+        f32: String,
+        /// The Rust type used for `double` fields.
+        f64: String,
+    },
+    /// Fail code generation, naming every `float` and `double` field.
+    Reject,
+}
@@ -0,0 +1,406 @@
+//! Errors reported while generating code from Protobuf descriptors.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use miette::{Diagnostic, SourceSpan};
+use prost_types::SourceCodeInfo;
+
+/// An error in the Protobuf sources, the descriptors or the configuration that prevents code
+/// generation.
+///
+/// Errors display as compiler diagnostics without their `error: ` prefix, which is left to the
+/// caller presenting them, quoting the offending line of the `.proto` file when it is known:
+///
+/// ```text
+/// field `.hash_map.Ledger.balances`: hash maps do not implement `TypeInfo`; use `Config::btree_map` for this field
+///  --> hash_map.proto:6:3
+///   |
+/// 6 |   map<string, uint64> balances = 1;
+///   |   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+///
+/// The same information is available as structured data through [`Error::location`] and
+/// [`Error::message`], for tools that present it themselves.
+///
+/// The methods of [`Config`](crate::Config) return [`std::io::Result`], so that build scripts can
+/// use `?` for code generation and file system errors alike. Errors of this type are wrapped in
+/// an [`std::io::Error`] of kind [`InvalidInput`](std::io::ErrorKind::InvalidInput), and can be
+/// recovered with [`Error::from_io_error`]:
+///
+/// ```rust,no_run
+/// # use std::io::Result;
+/// use ppsc_build::Location;
+///
+/// fn main() -> Result<()> {
+///   let result = ppsc_build::compile_protos(&["src/items.proto"], &["src"]);
+///   if let Some(error) = result.as_ref().err().and_then(ppsc_build::Error::from_io_error) {
+///     if let Some(Location { file, span: Some(span), .. }) = error.location() {
+///       eprintln!("{}:{}: {}", file, span.start_line + 1, error.message());
+///     }
+///   }
+///   result
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The `.proto` files could not be parsed, or do not form valid descriptors.
+    Compile {
+        /// The element the error was found at, when it is known.
+        location: Option<Location>,
+        /// The description of the error.
+        message: String,
+    },
+    /// Two Protobuf elements map to the same generated Rust item or SCALE variant index.
+    NameCollision {
+        /// The element that collides with a previous one.
+        location: Location,
+        /// The description of the error.
+        message: String,
+    },
+    /// A Protobuf type or value has no SCALE encoding with the configuration.
+    UnsupportedType {
+        /// The field or type that cannot be encoded.
+        location: Location,
+        /// The description of the error.
+        message: String,
+    },
+    /// A descriptor is malformed, or uses an unknown syntax or edition.
+    InvalidSyntax {
+        /// The malformed element, or the file for an unknown syntax or edition.
+        location: Location,
+        /// The description of the error.
+        message: String,
+    },
+    /// A path mapped with [`Config::extern_path`](crate::Config::extern_path) is invalid.
+    ExternPath {
+        /// The Protobuf path of the mapping, as configured.
+        proto_path: String,
+        /// The description of the error.
+        message: String,
+    },
+    /// An option set by name, with [`Config::set_option`](crate::Config::set_option), is
+    /// unknown or has an invalid value.
+    InvalidOption {
+        /// The name of the option, as given.
+        key: String,
+        /// The description of the error.
+        message: String,
+    },
+    /// A configuration file, loaded with [`Config::from_file`](crate::Config::from_file) or
+    /// [`Config::from_cargo_metadata`](crate::Config::from_cargo_metadata), is not valid TOML or
+    /// sets an invalid option.
+    ConfigFile {
+        /// The path of the configuration file.
+        file: String,
+        /// The description of the error.
+        message: String,
+    },
+}
+
+impl Error {
+    /// Returns the element the error was found at, unless it is a configuration error.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Compile { location, .. } => location.as_ref(),
+            Error::NameCollision { location, .. }
+            | Error::UnsupportedType { location, .. }
+            | Error::InvalidSyntax { location, .. } => Some(location),
+            Error::ExternPath { .. } | Error::InvalidOption { .. } | Error::ConfigFile { .. } => {
+                None
+            }
+        }
+    }
+
+    fn location_mut(&mut self) -> Option<&mut Location> {
+        match self {
+            Error::Compile { location, .. } => location.as_mut(),
+            Error::NameCollision { location, .. }
+            | Error::UnsupportedType { location, .. }
+            | Error::InvalidSyntax { location, .. } => Some(location),
+            Error::ExternPath { .. } | Error::InvalidOption { .. } | Error::ConfigFile { .. } => {
+                None
+            }
+        }
+    }
+
+    /// Returns the description of the error.
+    pub fn message(&self) -> &str {
+        match self {
+            Error::Compile { message, .. }
+            | Error::NameCollision { message, .. }
+            | Error::UnsupportedType { message, .. }
+            | Error::InvalidSyntax { message, .. }
+            | Error::ExternPath { message, .. }
+            | Error::InvalidOption { message, .. }
+            | Error::ConfigFile { message, .. } => message,
+        }
+    }
+
+    /// Returns the error wrapped in an [`std::io::Error`] returned by code generation, if any.
+    pub fn from_io_error(error: &io::Error) -> Option<&Error> {
+        error.get_ref()?.downcast_ref()
+    }
+
+    /// Like [`from_io_error`](Self::from_io_error), for modifying the error.
+    pub(crate) fn from_io_error_mut(error: &mut io::Error) -> Option<&mut Error> {
+        error.get_mut()?.downcast_mut()
+    }
+
+    /// Converts an error from compiling `.proto` files with `protox`, locating it with the first
+    /// span it labels.
+    pub(crate) fn from_protox(error: &protox::Error) -> Error {
+        let location = error.file().map(|file| Location {
+            file: file.to_owned(),
+            path: String::new(),
+            span: protox_span(error),
+            snippet: None,
+        });
+        Error::Compile {
+            location,
+            message: error.to_string(),
+        }
+    }
+
+    /// Quotes the line the error is located at, from the first of `includes` that contains the
+    /// `.proto` file.
+    pub(crate) fn add_snippet(&mut self, includes: &[impl AsRef<Path>]) {
+        let Some(location) = self.location_mut() else {
+            return;
+        };
+        let Some(span) = location.span else {
+            return;
+        };
+        location.snippet = includes
+            .iter()
+            .find_map(|include| fs::read_to_string(include.as_ref().join(&location.file)).ok())
+            .and_then(|source| {
+                source
+                    .lines()
+                    .nth(span.start_line)
+                    .map(|line| line.trim_end().to_owned())
+            });
+    }
+}
+
+/// Returns the span of the first label of a `protox` error, converted from byte offsets.
+fn protox_span(error: &protox::Error) -> Option<Span> {
+    let label = error.labels()?.next()?;
+    let source = error.source_code()?;
+    let position = |offset: usize| {
+        source
+            .read_span(&SourceSpan::from((offset, 0)), 0, 0)
+            .ok()
+            .map(|contents| (contents.line(), contents.column()))
+    };
+    let (start_line, start_column) = position(label.offset())?;
+    let (end_line, end_column) = position(label.offset() + label.len())?;
+    Some(Span {
+        start_line,
+        start_column,
+        end_line,
+        end_column,
+    })
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())?;
+        let Some(location) = self.location() else {
+            if let Error::ConfigFile { file, .. } = self {
+                write!(f, "\n --> {}", file)?;
+            }
+            return Ok(());
+        };
+        let Some(span) = location.span else {
+            return write!(f, "\n --> {}", location.file);
+        };
+
+        let line_number = (span.start_line + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+        write!(
+            f,
+            "\n{}--> {}:{}:{}",
+            gutter,
+            location.file,
+            line_number,
+            span.start_column + 1
+        )?;
+        let Some(snippet) = &location.snippet else {
+            return Ok(());
+        };
+
+        // Underline the span up to the end of its first line, keeping tabs so that the carets
+        // line up with the snippet.
+        let indent: String = snippet
+            .chars()
+            .take(span.start_column)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let end_column = if span.end_line == span.start_line {
+            span.end_column
+        } else {
+            snippet.chars().count()
+        };
+        let carets = "^".repeat(end_column.saturating_sub(span.start_column).max(1));
+        write!(
+            f,
+            "\n{gutter} |\n{line_number} | {snippet}\n{gutter} | {indent}{carets}"
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
+/// An element of a `.proto` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// The name of the file, relative to its include directory.
+    pub file: String,
+    /// The fully-qualified Protobuf path of the element, such as `.package.Message.field`, the
+    /// package of the file for errors about the file itself, or empty when it is unknown.
+    pub path: String,
+    /// The span of the element, when the descriptors include source code info.
+    pub span: Option<Span>,
+    /// The line of the file the span starts on, when the file could be read.
+    pub snippet: Option<String>,
+}
+
+/// A span of a `.proto` file, with zero-based lines and columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// Converts the `span` of a `SourceCodeInfo` location, which omits the end line when it is
+    /// the start line.
+    pub(crate) fn from_source_span(span: &[i32]) -> Option<Span> {
+        let span: Vec<usize> = span
+            .iter()
+            .map(|&value| usize::try_from(value).ok())
+            .collect::<Option<_>>()?;
+        match span[..] {
+            [line, start_column, end_column] => Some(Span {
+                start_line: line,
+                start_column,
+                end_line: line,
+                end_column,
+            }),
+            [start_line, start_column, end_line, end_column] => Some(Span {
+                start_line,
+                start_column,
+                end_line,
+                end_column,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the span of the element at `path` in the source code info of a file.
+    pub(crate) fn find(source_info: Option<&SourceCodeInfo>, path: &[i32]) -> Option<Span> {
+        source_info?
+            .location
+            .iter()
+            .find(|location| location.path == path)
+            .and_then(|location| Span::from_source_span(&location.span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_from_source_span() {
+        assert_eq!(
+            Span::from_source_span(&[3, 2, 10]),
+            Some(Span {
+                start_line: 3,
+                start_column: 2,
+                end_line: 3,
+                end_column: 10,
+            })
+        );
+        assert_eq!(
+            Span::from_source_span(&[3, 2, 5, 1]),
+            Some(Span {
+                start_line: 3,
+                start_column: 2,
+                end_line: 5,
+                end_column: 1,
+            })
+        );
+        assert_eq!(Span::from_source_span(&[]), None);
+        assert_eq!(Span::from_source_span(&[-1, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let location = Location {
+            file: String::from("items.proto"),
+            path: String::from(".items.Item.price"),
+            span: Some(Span {
+                start_line: 11,
+                start_column: 2,
+                end_line: 11,
+                end_column: 21,
+            }),
+            snippet: Some(String::from("  double price = 3;")),
+        };
+        let error = Error::UnsupportedType {
+            location: location.clone(),
+            message: String::from("no encoding"),
+        };
+        assert_eq!(
+            error.to_string(),
+            "no encoding\n  --> items.proto:12:3\n   |\n12 |   double price = 3;\n   |   ^^^^^^^^^^^^^^^^^^^"
+        );
+
+        let error = Error::UnsupportedType {
+            location: Location {
+                snippet: None,
+                ..location.clone()
+            },
+            message: String::from("no encoding"),
+        };
+        assert_eq!(error.to_string(), "no encoding\n  --> items.proto:12:3");
+
+        let error = Error::Compile {
+            location: Some(Location {
+                span: None,
+                ..location
+            }),
+            message: String::from("invalid"),
+        };
+        assert_eq!(error.to_string(), "invalid\n --> items.proto");
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let error = Error::ExternPath {
+            proto_path: String::from("foo"),
+            message: String::from("invalid"),
+        };
+        let io_error = io::Error::from(error.clone());
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(io_error.to_string(), "invalid");
+        assert_eq!(Error::from_io_error(&io_error), Some(&error));
+        assert_eq!(
+            Error::from_io_error(&io::Error::new(io::ErrorKind::InvalidInput, "other")),
+            None
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::{HashMap, hash_map};
 
 use itertools::Itertools;
 
+use crate::Error;
 use crate::ident::{to_snake, to_upper_camel};
 
 fn validate_proto_path(path: &str) -> Result<(), String> {
@@ -23,13 +24,18 @@ pub struct ExternPaths {
 }
 
 impl ExternPaths {
-    pub fn new(paths: &[(String, String)]) -> Result<ExternPaths, String> {
+    pub fn new(paths: &[(String, String)]) -> std::io::Result<ExternPaths> {
         let mut extern_paths = ExternPaths {
             extern_paths: HashMap::new(),
         };
 
         for (proto_path, rust_path) in paths {
-            extern_paths.insert(proto_path.clone(), rust_path.clone())?;
+            extern_paths
+                .insert(proto_path.clone(), rust_path.clone())
+                .map_err(|message| Error::ExternPath {
+                    proto_path: proto_path.clone(),
+                    message,
+                })?;
         }
 
         Ok(extern_paths)
@@ -0,0 +1,382 @@
+//! Resolution of Protobuf Editions features.
+//!
+//! Every file has an edition: proto2 and proto3 files behave like editions with fixed feature
+//! defaults, and Editions files can override features on the file, on messages, on fields and on
+//! enums. An element inherits the resolved features of its parent.
+//!
+//! `prost-types` does not retain the `edition` of a file or the `features` of any options, so they
+//! are decoded separately from an encoded `FileDescriptorSet` into [`DescriptorFeatures`].
+
+use std::collections::HashMap;
+
+use prost::Message;
+use prost_types::FileDescriptorProto;
+
+/// The edition of a Protobuf file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Edition {
+    Proto2,
+    Proto3,
+    Edition2023,
+    Edition2024,
+}
+
+impl Edition {
+    /// Returns the edition of a file from its `syntax` and, for Editions files, its `edition`.
+    ///
+    /// Editions files whose edition is not known default to Edition 2023.
+    pub(crate) fn of(file: &FileDescriptorProto, edition: Option<i32>) -> Result<Edition, String> {
+        match file.syntax.as_deref() {
+            None | Some("proto2") => Ok(Edition::Proto2),
+            Some("proto3") => Ok(Edition::Proto3),
+            Some("editions") => match edition {
+                None | Some(1000) => Ok(Edition::Edition2023),
+                Some(1001) => Ok(Edition::Edition2024),
+                Some(edition) => Err(format!(
+                    "file `{}`: unsupported edition {}",
+                    file.name(),
+                    edition
+                )),
+            },
+            Some(syntax) => Err(format!(
+                "file `{}`: unknown syntax `{}`",
+                file.name(),
+                syntax
+            )),
+        }
+    }
+}
+
+/// Whether singular fields track presence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FieldPresence {
+    /// Unset fields are distinguished from fields set to their default, like proto2 `optional`.
+    Explicit,
+    /// Fields set to their default are indistinguishable from unset fields, like proto3 fields.
+    Implicit,
+    /// Fields are required, like proto2 `required`.
+    LegacyRequired,
+}
+
+/// Whether enums accept values without a declared variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EnumType {
+    /// Unknown values are accepted, and the zero value is the default.
+    Open,
+    /// Only declared values are accepted, and the first declared value is the default.
+    Closed,
+}
+
+/// How repeated scalar fields are encoded on the Protobuf wire.
+///
+/// SCALE encodes repeated fields the same way either way; the feature is resolved so that
+/// Editions files setting it are accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RepeatedFieldEncoding {
+    Packed,
+    Expanded,
+}
+
+/// The resolved features of a field or enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Features {
+    pub(crate) field_presence: FieldPresence,
+    pub(crate) enum_type: EnumType,
+    pub(crate) repeated_field_encoding: RepeatedFieldEncoding,
+}
+
+impl Features {
+    /// The features of elements in a file of the given edition that override none.
+    pub(crate) fn defaults(edition: Edition) -> Features {
+        match edition {
+            Edition::Proto2 => Features {
+                field_presence: FieldPresence::Explicit,
+                enum_type: EnumType::Closed,
+                repeated_field_encoding: RepeatedFieldEncoding::Expanded,
+            },
+            Edition::Proto3 => Features {
+                field_presence: FieldPresence::Implicit,
+                enum_type: EnumType::Open,
+                repeated_field_encoding: RepeatedFieldEncoding::Packed,
+            },
+            Edition::Edition2023 | Edition::Edition2024 => Features {
+                field_presence: FieldPresence::Explicit,
+                enum_type: EnumType::Open,
+                repeated_field_encoding: RepeatedFieldEncoding::Packed,
+            },
+        }
+    }
+
+    /// Returns these features with the ones set in `overrides` replaced.
+    pub(crate) fn merge(self, overrides: Option<&FeatureSet>) -> Features {
+        let Some(overrides) = overrides else {
+            return self;
+        };
+        Features {
+            field_presence: match overrides.field_presence {
+                Some(1) => FieldPresence::Explicit,
+                Some(2) => FieldPresence::Implicit,
+                Some(3) => FieldPresence::LegacyRequired,
+                _ => self.field_presence,
+            },
+            enum_type: match overrides.enum_type {
+                Some(1) => EnumType::Open,
+                Some(2) => EnumType::Closed,
+                _ => self.enum_type,
+            },
+            repeated_field_encoding: match overrides.repeated_field_encoding {
+                Some(1) => RepeatedFieldEncoding::Packed,
+                Some(2) => RepeatedFieldEncoding::Expanded,
+                _ => self.repeated_field_encoding,
+            },
+        }
+    }
+}
+
+/// The subset of `google.protobuf.FeatureSet` that affects code generation.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct FeatureSet {
+    #[prost(int32, optional, tag = "1")]
+    pub(crate) field_presence: Option<i32>,
+    #[prost(int32, optional, tag = "2")]
+    pub(crate) enum_type: Option<i32>,
+    #[prost(int32, optional, tag = "3")]
+    pub(crate) repeated_field_encoding: Option<i32>,
+}
+
+/// The editions and feature overrides of the files in a `FileDescriptorSet`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DescriptorFeatures {
+    /// The edition of each Editions file, by file name.
+    editions: HashMap<String, i32>,
+    /// The features set on each file, by file name.
+    files: HashMap<String, FeatureSet>,
+    /// The features set on each message, field and enum, by fully-qualified name.
+    elements: HashMap<String, FeatureSet>,
+    /// Whether the features were decoded, rather than left empty for descriptors that do not
+    /// retain them.
+    decoded: bool,
+}
+
+impl DescriptorFeatures {
+    /// Decodes the editions and features of an encoded `FileDescriptorSet`.
+    pub(crate) fn decode(encoded_fds: &[u8]) -> Result<DescriptorFeatures, prost::DecodeError> {
+        Ok(DescriptorFeatures::from_files(
+            RawFileDescriptorSet::decode(encoded_fds)?.file,
+        ))
+    }
+
+    /// Decodes the editions and features of the files in an encoded `CodeGeneratorRequest`.
+    pub(crate) fn decode_plugin_request(
+        encoded_request: &[u8],
+    ) -> Result<DescriptorFeatures, prost::DecodeError> {
+        Ok(DescriptorFeatures::from_files(
+            RawCodeGeneratorRequest::decode(encoded_request)?.proto_file,
+        ))
+    }
+
+    fn from_files(files: Vec<RawFileDescriptor>) -> DescriptorFeatures {
+        let mut features = DescriptorFeatures {
+            decoded: true,
+            ..DescriptorFeatures::default()
+        };
+        for file in files {
+            let package = match &file.package {
+                Some(package) => format!(".{}", package),
+                None => String::new(),
+            };
+            if let Some(edition) = file.edition {
+                features.editions.insert(file.name.clone(), edition);
+            }
+            if let Some(file_features) = file.options.and_then(|options| options.features) {
+                features.files.insert(file.name.clone(), file_features);
+            }
+            for message in file.message_type {
+                features.add_message(&package, message);
+            }
+            features.add_enums(&package, file.enum_type);
+        }
+        features
+    }
+
+    fn add_message(&mut self, scope: &str, message: RawDescriptor) {
+        let name = format!("{}.{}", scope, message.name);
+        for field in message.field {
+            if let Some(field_features) = field.options.and_then(|options| options.features) {
+                self.elements
+                    .insert(format!("{}.{}", name, field.name), field_features);
+            }
+        }
+        for nested in message.nested_type {
+            self.add_message(&name, nested);
+        }
+        self.add_enums(&name, message.enum_type);
+        if let Some(message_features) = message.options.and_then(|options| options.features) {
+            self.elements.insert(name, message_features);
+        }
+    }
+
+    fn add_enums(&mut self, scope: &str, enums: Vec<RawEnumDescriptor>) {
+        for enum_type in enums {
+            if let Some(enum_features) = enum_type.options.and_then(|options| options.features) {
+                self.elements
+                    .insert(format!("{}.{}", scope, enum_type.name), enum_features);
+            }
+        }
+    }
+
+    /// Returns whether the features were decoded from the encoded descriptors.
+    pub(crate) fn is_decoded(&self) -> bool {
+        self.decoded
+    }
+
+    /// Returns the edition of the named file, if it is an Editions file whose edition is known.
+    pub(crate) fn edition(&self, file_name: &str) -> Option<i32> {
+        self.editions.get(file_name).copied()
+    }
+
+    /// Returns the features set on the named file.
+    pub(crate) fn file(&self, file_name: &str) -> Option<&FeatureSet> {
+        self.files.get(file_name)
+    }
+
+    /// Returns the features set on the message, field or enum with the fully-qualified name.
+    pub(crate) fn element(&self, fq_name: &str) -> Option<&FeatureSet> {
+        self.elements.get(fq_name)
+    }
+}
+
+// Partial mirrors of the descriptor messages, holding only what `prost-types` drops.
+
+#[derive(Clone, PartialEq, Message)]
+struct RawFileDescriptorSet {
+    #[prost(message, repeated, tag = "1")]
+    file: Vec<RawFileDescriptor>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawCodeGeneratorRequest {
+    #[prost(message, repeated, tag = "15")]
+    proto_file: Vec<RawFileDescriptor>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawFileDescriptor {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(string, optional, tag = "2")]
+    package: Option<String>,
+    #[prost(message, repeated, tag = "4")]
+    message_type: Vec<RawDescriptor>,
+    #[prost(message, repeated, tag = "5")]
+    enum_type: Vec<RawEnumDescriptor>,
+    #[prost(message, optional, tag = "8")]
+    options: Option<RawFileOptions>,
+    #[prost(int32, optional, tag = "14")]
+    edition: Option<i32>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawFileOptions {
+    #[prost(message, optional, tag = "50")]
+    features: Option<FeatureSet>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawDescriptor {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(message, repeated, tag = "2")]
+    field: Vec<RawFieldDescriptor>,
+    #[prost(message, repeated, tag = "3")]
+    nested_type: Vec<RawDescriptor>,
+    #[prost(message, repeated, tag = "4")]
+    enum_type: Vec<RawEnumDescriptor>,
+    #[prost(message, optional, tag = "7")]
+    options: Option<RawMessageOptions>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawMessageOptions {
+    #[prost(message, optional, tag = "12")]
+    features: Option<FeatureSet>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawFieldDescriptor {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(message, optional, tag = "8")]
+    options: Option<RawFieldOptions>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawFieldOptions {
+    #[prost(message, optional, tag = "21")]
+    features: Option<FeatureSet>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawEnumDescriptor {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(message, optional, tag = "3")]
+    options: Option<RawEnumOptions>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct RawEnumOptions {
+    #[prost(message, optional, tag = "7")]
+    features: Option<FeatureSet>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edition_of() {
+        let file = |syntax: Option<&str>| FileDescriptorProto {
+            name: Some(String::from("test.proto")),
+            syntax: syntax.map(String::from),
+            ..FileDescriptorProto::default()
+        };
+
+        assert_eq!(Edition::of(&file(None), None), Ok(Edition::Proto2));
+        assert_eq!(
+            Edition::of(&file(Some("proto3")), None),
+            Ok(Edition::Proto3)
+        );
+        assert_eq!(
+            Edition::of(&file(Some("editions")), None),
+            Ok(Edition::Edition2023)
+        );
+        assert_eq!(
+            Edition::of(&file(Some("editions")), Some(1001)),
+            Ok(Edition::Edition2024)
+        );
+        assert!(Edition::of(&file(Some("editions")), Some(9999)).is_err());
+        assert!(Edition::of(&file(Some("proto4")), None).is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let file = Features::defaults(Edition::Edition2023).merge(Some(&FeatureSet {
+            field_presence: Some(2),
+            ..FeatureSet::default()
+        }));
+        assert_eq!(file.field_presence, FieldPresence::Implicit);
+        assert_eq!(file.enum_type, EnumType::Open);
+
+        let field = file.merge(Some(&FeatureSet {
+            repeated_field_encoding: Some(2),
+            ..FeatureSet::default()
+        }));
+        assert_eq!(field.field_presence, FieldPresence::Implicit);
+        assert_eq!(
+            field.repeated_field_encoding,
+            RepeatedFieldEncoding::Expanded
+        );
+        assert_eq!(field.merge(None), field);
+    }
+}
@@ -1,4 +1,5 @@
 // This file is @generated by ppsc-build.
+extern crate alloc;
 pub mod a {
     include!("a.rs");
 }
@@ -0,0 +1,31 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Blob {
+    pub data: ::bytes::Bytes,
+    pub checksum: Option<::bytes::Bytes>,
+    pub chunks: alloc::vec::Vec<::bytes::Bytes>,
+    pub metadata: alloc::collections::BTreeMap<alloc::string::String, ::bytes::Bytes>,
+    pub source: Option<blob::Source>,
+}
+impl Blob {
+    /// Returns the value of `checksum`, or the default value if `checksum` is unset.
+    pub fn checksum(&self) -> &[u8] {
+        match &self.checksum {
+            Some(value) => &value[..],
+            None => b"",
+        }
+    }
+}
+/// Nested message and enum types in `Blob`.
+pub mod blob {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Source {
+        Inline(::bytes::Bytes),
+        Url(alloc::string::String),
+    }
+}
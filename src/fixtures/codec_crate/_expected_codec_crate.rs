@@ -0,0 +1,156 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use ::codec::{Encode, Decode};
+use ::scale_info::TypeInfo;
+
+#[derive(Encode, Decode, Debug, TypeInfo)]
+#[codec(crate = ::codec)]
+#[scale_info(capture_docs = "always")]
+#[scale_info(crate = ::scale_info)]
+pub struct Transfer {
+    #[codec(compact)]
+    pub amount: u64,
+    pub offset: ZigZagI32,
+    pub fee: F32Bits,
+    pub kind: i32,
+    pub nonces: alloc::vec::Vec<::codec::Compact<u32>>,
+    pub memo: Option<transfer::Memo>,
+}
+impl Default for Transfer {
+    fn default() -> Self {
+        Self {
+            amount: Default::default(),
+            offset: Default::default(),
+            fee: Default::default(),
+            kind: 0,
+            nonces: Default::default(),
+            memo: None,
+        }
+    }
+}
+/// Nested message and enum types in `Transfer`.
+pub mod transfer {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, TypeInfo)]
+    #[codec(crate = ::codec)]
+    #[scale_info(capture_docs = "always")]
+    #[scale_info(crate = ::scale_info)]
+    pub enum Memo {
+        Reference(#[codec(compact)] u64),
+        Note(alloc::string::String),
+    }
+}
+#[derive(Encode, Decode, Debug, ::codec::MaxEncodedLen, TypeInfo, Default)]
+#[codec(crate = ::codec)]
+#[scale_info(capture_docs = "always")]
+#[scale_info(crate = ::scale_info)]
+pub enum Kind {
+    #[codec(index = 0)]
+    #[default]
+    Unknown = 0,
+    #[codec(index = 1)]
+    Transfer = 1,
+}
+impl Kind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "KIND_UNKNOWN",
+            Self::Transfer => "KIND_TRANSFER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "KIND_UNKNOWN" => Some(Self::Unknown),
+            "KIND_TRANSFER" => Some(Self::Transfer),
+            _ => None,
+        }
+    }
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI32(pub i32);
+
+impl ZigZagI32 {
+    fn zigzag(self) -> u32 {
+        ((self.0 << 1) ^ (self.0 >> 31)) as u32
+    }
+}
+
+impl Encode for ZigZagI32 {
+    fn size_hint(&self) -> usize {
+        ::codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: ::codec::Output + ?Sized>(&self, dest: &mut T) {
+        ::codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl ::codec::EncodeLike for ZigZagI32 {}
+
+impl Decode for ZigZagI32 {
+    fn decode<I: ::codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, ::codec::Error> {
+        let value = ::codec::Compact::<u32>::decode(input)?.0;
+        Ok(ZigZagI32((value >> 1) as i32 ^ -((value & 1) as i32)))
+    }
+}
+
+impl ::scale_info::TypeInfo for ZigZagI32 {
+    type Identity = Self;
+
+    fn type_info() -> ::scale_info::Type {
+        ::scale_info::Type::builder()
+            .path(::scale_info::Path::new("ZigZagI32", module_path!()))
+            .composite(
+                ::scale_info::build::Fields::unnamed()
+                    .field(|f| f.compact::<u32>().type_name("u32")),
+            )
+    }
+}
+
+impl ::codec::MaxEncodedLen for ZigZagI32 {
+    fn max_encoded_len() -> usize {
+        ::codec::Compact::<u32>::max_encoded_len()
+    }
+}
+
+/// A `f32` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode, ::codec::MaxEncodedLen, TypeInfo)]
+#[codec(crate = ::codec)]
+#[scale_info(crate = ::scale_info)]
+pub struct F32Bits(pub u32);
+
+impl F32Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f32 {
+        f32::from_bits(self.0)
+    }
+}
+
+impl From<f32> for F32Bits {
+    fn from(value: f32) -> Self {
+        F32Bits(value.to_bits())
+    }
+}
+
+impl From<F32Bits> for f32 {
+    fn from(value: F32Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F32Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
@@ -0,0 +1,35 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Transfer {
+    #[codec(compact)]
+    pub id: u64,
+    #[codec(compact)]
+    pub nonce: u32,
+    pub fee: Option<parity_scale_codec::Compact<u64>>,
+    pub amounts: alloc::vec::Vec<parity_scale_codec::Compact<u32>>,
+    pub balances: alloc::collections::BTreeMap<alloc::string::String, parity_scale_codec::Compact<u64>>,
+    pub delta: i64,
+    pub memo: Option<transfer::Memo>,
+}
+impl Transfer {
+    /// Returns the value of `fee`, or the default value if `fee` is unset.
+    pub fn fee(&self) -> u64 {
+        match &self.fee {
+            Some(value) => value.0,
+            None => 0,
+        }
+    }
+}
+/// Nested message and enum types in `Transfer`.
+pub mod transfer {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Memo {
+        Reference(#[codec(compact)] u32),
+        Note(alloc::string::String),
+    }
+}
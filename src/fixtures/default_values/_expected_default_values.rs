@@ -0,0 +1,276 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub retries: Option<ZigZagI32>,
+    pub offset: Option<ZigZagI64>,
+    pub limit: Option<parity_scale_codec::Compact<u64>>,
+    pub ratio: Option<F32Bits>,
+    pub threshold: Option<F64Bits>,
+    pub enabled: Option<bool>,
+    pub name: Option<alloc::string::String>,
+    pub magic: Option<alloc::vec::Vec<u8>>,
+    pub priority: Option<Priority>,
+    pub fallback: Option<i32>,
+    pub timeout: Option<parity_scale_codec::Compact<u32>>,
+    pub version: ZigZagI32,
+    pub level: Priority,
+    pub tag: [u8; 4],
+    pub ports: alloc::vec::Vec<ZigZagI32>,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            retries: None,
+            offset: None,
+            limit: None,
+            ratio: None,
+            threshold: None,
+            enabled: None,
+            name: None,
+            magic: None,
+            priority: None,
+            fallback: None,
+            timeout: None,
+            version: ZigZagI32(2),
+            level: Priority::High,
+            tag: *b"abcd",
+            ports: Default::default(),
+        }
+    }
+}
+impl Settings {
+    /// Returns the value of `retries`, or the default value if `retries` is unset.
+    pub fn retries(&self) -> i32 {
+        match &self.retries {
+            Some(value) => value.0,
+            None => 3,
+        }
+    }
+    /// Returns the value of `offset`, or the default value if `offset` is unset.
+    pub fn offset(&self) -> i64 {
+        match &self.offset {
+            Some(value) => value.0,
+            None => -42,
+        }
+    }
+    /// Returns the value of `limit`, or the default value if `limit` is unset.
+    pub fn limit(&self) -> u64 {
+        match &self.limit {
+            Some(value) => value.0,
+            None => 1000,
+        }
+    }
+    /// Returns the value of `ratio`, or the default value if `ratio` is unset.
+    pub fn ratio(&self) -> f32 {
+        match &self.ratio {
+            Some(value) => value.get(),
+            None => 0.5f32,
+        }
+    }
+    /// Returns the value of `threshold`, or the default value if `threshold` is unset.
+    pub fn threshold(&self) -> f64 {
+        match &self.threshold {
+            Some(value) => value.get(),
+            None => f64::INFINITY,
+        }
+    }
+    /// Returns the value of `enabled`, or the default value if `enabled` is unset.
+    pub fn enabled(&self) -> bool {
+        match &self.enabled {
+            Some(value) => *value,
+            None => true,
+        }
+    }
+    /// Returns the value of `name`, or the default value if `name` is unset.
+    pub fn name(&self) -> &str {
+        match &self.name {
+            Some(value) => value.as_str(),
+            None => "unnamed \"settings\"",
+        }
+    }
+    /// Returns the value of `magic`, or the default value if `magic` is unset.
+    pub fn magic(&self) -> &[u8] {
+        match &self.magic {
+            Some(value) => &value[..],
+            None => b"\x00\x01PPSC\x0a",
+        }
+    }
+    /// Returns the value of `priority`, or the default value if `priority` is unset.
+    pub fn priority(&self) -> &Priority {
+        match &self.priority {
+            Some(value) => value,
+            None => &Priority::Normal,
+        }
+    }
+    /// Returns the value of `fallback`, or the default value if `fallback` is unset.
+    pub fn fallback(&self) -> i32 {
+        match &self.fallback {
+            Some(value) => *value,
+            None => 1,
+        }
+    }
+    /// Returns the value of `timeout`, or the default value if `timeout` is unset.
+    pub fn timeout(&self) -> u32 {
+        match &self.timeout {
+            Some(value) => value.0,
+            None => 0,
+        }
+    }
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Priority {
+    #[codec(index = 1)]
+    #[default]
+    High = 1,
+    #[codec(index = 2)]
+    Normal = 2,
+    #[codec(index = 3)]
+    Low = 3,
+}
+impl Priority {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::High => "PRIORITY_HIGH",
+            Self::Normal => "PRIORITY_NORMAL",
+            Self::Low => "PRIORITY_LOW",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "PRIORITY_HIGH" => Some(Self::High),
+            "PRIORITY_NORMAL" => Some(Self::Normal),
+            "PRIORITY_LOW" => Some(Self::Low),
+            _ => None,
+        }
+    }
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI32(pub i32);
+
+impl ZigZagI32 {
+    fn zigzag(self) -> u32 {
+        ((self.0 << 1) ^ (self.0 >> 31)) as u32
+    }
+}
+
+impl Encode for ZigZagI32 {
+    fn size_hint(&self) -> usize {
+        parity_scale_codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        parity_scale_codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl parity_scale_codec::EncodeLike for ZigZagI32 {}
+
+impl Decode for ZigZagI32 {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        let value = parity_scale_codec::Compact::<u32>::decode(input)?.0;
+        Ok(ZigZagI32((value >> 1) as i32 ^ -((value & 1) as i32)))
+    }
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI64(pub i64);
+
+impl ZigZagI64 {
+    fn zigzag(self) -> u64 {
+        ((self.0 << 1) ^ (self.0 >> 63)) as u64
+    }
+}
+
+impl Encode for ZigZagI64 {
+    fn size_hint(&self) -> usize {
+        parity_scale_codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        parity_scale_codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl parity_scale_codec::EncodeLike for ZigZagI64 {}
+
+impl Decode for ZigZagI64 {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        let value = parity_scale_codec::Compact::<u64>::decode(input)?.0;
+        Ok(ZigZagI64((value >> 1) as i64 ^ -((value & 1) as i64)))
+    }
+}
+
+/// A `f32` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct F32Bits(pub u32);
+
+impl F32Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f32 {
+        f32::from_bits(self.0)
+    }
+}
+
+impl From<f32> for F32Bits {
+    fn from(value: f32) -> Self {
+        F32Bits(value.to_bits())
+    }
+}
+
+impl From<F32Bits> for f32 {
+    fn from(value: F32Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F32Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+/// A `f64` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct F64Bits(pub u64);
+
+impl F64Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f64 {
+        f64::from_bits(self.0)
+    }
+}
+
+impl From<f64> for F64Bits {
+    fn from(value: f64) -> Self {
+        F64Bits(value.to_bits())
+    }
+}
+
+impl From<F64Bits> for f64 {
+    fn from(value: F64Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F64Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
@@ -0,0 +1,143 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Plain {
+    pub id: u64,
+    pub name: alloc::string::String,
+    pub kind: i32,
+}
+impl Default for Plain {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            name: Default::default(),
+            kind: 0,
+        }
+    }
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    pub latitude: F64Bits,
+    pub longitude: F64Bits,
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Place {
+    pub name: alloc::string::String,
+    pub position: Option<Position>,
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Index {
+    pub entries: alloc::collections::BTreeMap<alloc::string::String, u32>,
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Counters {
+    pub values: alloc::vec::Vec<parity_scale_codec::Compact<u64>>,
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Tree {
+    pub value: Option<Plain>,
+    pub children: alloc::vec::Vec<Tree>,
+    pub annotation: Option<tree::Annotation>,
+}
+/// Nested message and enum types in `Tree`.
+pub mod tree {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Annotation {
+        Label(alloc::string::String),
+        Weight(F32Bits),
+    }
+}
+#[derive(Encode, Decode, Clone, Default)]
+pub struct Custom {
+    pub id: u32,
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Kind {
+    #[codec(index = 0)]
+    #[default]
+    Unspecified = 0,
+    #[codec(index = 1)]
+    Leaf = 1,
+}
+impl Kind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "KIND_UNSPECIFIED",
+            Self::Leaf => "KIND_LEAF",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "KIND_LEAF" => Some(Self::Leaf),
+            _ => None,
+        }
+    }
+}
+
+/// A `f32` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct F32Bits(pub u32);
+
+impl F32Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f32 {
+        f32::from_bits(self.0)
+    }
+}
+
+impl From<f32> for F32Bits {
+    fn from(value: f32) -> Self {
+        F32Bits(value.to_bits())
+    }
+}
+
+impl From<F32Bits> for f32 {
+    fn from(value: F32Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F32Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+/// A `f64` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct F64Bits(pub u64);
+
+impl F64Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f64 {
+        f64::from_bits(self.0)
+    }
+}
+
+impl From<f64> for F64Bits {
+    fn from(value: f64) -> Self {
+        F64Bits(value.to_bits())
+    }
+}
+
+impl From<F64Bits> for f64 {
+    fn from(value: F64Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F64Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
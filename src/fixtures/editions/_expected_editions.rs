@@ -0,0 +1,101 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug)]
+pub struct Account {
+    pub id: i32,
+    pub name: Option<alloc::string::String>,
+    pub balance: i64,
+    pub status: Status,
+    pub level: Option<Level>,
+    pub tags: alloc::vec::Vec<i32>,
+    pub parent: Option<alloc::boxed::Box<Account>>,
+}
+impl Default for Account {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            name: None,
+            balance: 100,
+            status: Status::Unknown,
+            level: None,
+            tags: Default::default(),
+            parent: None,
+        }
+    }
+}
+impl Account {
+    /// Returns the value of `name`, or the default value if `name` is unset.
+    pub fn name(&self) -> &str {
+        match &self.name {
+            Some(value) => value.as_str(),
+            None => "",
+        }
+    }
+    /// Returns the value of `level`, or the default value if `level` is unset.
+    pub fn level(&self) -> &Level {
+        match &self.level {
+            Some(value) => value,
+            None => &Level::High,
+        }
+    }
+}
+/// An open enum, defaulting to its zero value.
+#[derive(Encode, Decode, Debug, Default)]
+pub enum Status {
+    #[codec(index = 0)]
+    #[default]
+    Unknown = 0,
+    #[codec(index = 1)]
+    Active = 1,
+}
+impl Status {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "STATUS_UNKNOWN",
+            Self::Active => "STATUS_ACTIVE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "STATUS_UNKNOWN" => Some(Self::Unknown),
+            "STATUS_ACTIVE" => Some(Self::Active),
+            _ => None,
+        }
+    }
+}
+/// A closed enum, defaulting to its first value.
+#[derive(Encode, Decode, Debug, Default)]
+pub enum Level {
+    #[codec(index = 3)]
+    #[default]
+    High = 3,
+    #[codec(index = 4)]
+    Low = 4,
+}
+impl Level {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::High => "LEVEL_HIGH",
+            Self::Low => "LEVEL_LOW",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "LEVEL_HIGH" => Some(Self::High),
+            "LEVEL_LOW" => Some(Self::Low),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Reading {
+    #[codec(compact)]
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub offset: ZigZagI64,
+    pub delta: ZigZagI32,
+    pub calibration: i32,
+    pub sensor: Option<parity_scale_codec::Compact<u32>>,
+    pub samples: alloc::vec::Vec<ZigZagI32>,
+    pub drift: alloc::collections::BTreeMap<u32, ZigZagI64>,
+    pub valid: bool,
+    pub source: Option<reading::Source>,
+}
+impl Reading {
+    /// Returns the value of `sensor`, or the default value if `sensor` is unset.
+    pub fn sensor(&self) -> u32 {
+        match &self.sensor {
+            Some(value) => value.0,
+            None => 0,
+        }
+    }
+}
+/// Nested message and enum types in `Reading`.
+pub mod reading {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Source {
+        Channel(#[codec(compact)] u32),
+        Address(u32),
+        Correction(ZigZagI64),
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Summary {
+    #[codec(compact)]
+    pub count: u32,
+    pub total: ZigZagI64,
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI32(pub i32);
+
+impl ZigZagI32 {
+    fn zigzag(self) -> u32 {
+        ((self.0 << 1) ^ (self.0 >> 31)) as u32
+    }
+}
+
+impl Encode for ZigZagI32 {
+    fn size_hint(&self) -> usize {
+        parity_scale_codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        parity_scale_codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl parity_scale_codec::EncodeLike for ZigZagI32 {}
+
+impl Decode for ZigZagI32 {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        let value = parity_scale_codec::Compact::<u32>::decode(input)?.0;
+        Ok(ZigZagI32((value >> 1) as i32 ^ -((value & 1) as i32)))
+    }
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI64(pub i64);
+
+impl ZigZagI64 {
+    fn zigzag(self) -> u64 {
+        ((self.0 << 1) ^ (self.0 >> 63)) as u64
+    }
+}
+
+impl Encode for ZigZagI64 {
+    fn size_hint(&self) -> usize {
+        parity_scale_codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        parity_scale_codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl parity_scale_codec::EncodeLike for ZigZagI64 {}
+
+impl Decode for ZigZagI64 {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        let value = parity_scale_codec::Compact::<u64>::decode(input)?.0;
+        Ok(ZigZagI64((value >> 1) as i64 ^ -((value & 1) as i64)))
+    }
+}
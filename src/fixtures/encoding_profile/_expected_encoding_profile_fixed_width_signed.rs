@@ -0,0 +1,44 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Reading {
+    #[codec(compact)]
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub offset: i64,
+    pub delta: i32,
+    pub calibration: i32,
+    pub sensor: Option<parity_scale_codec::Compact<u32>>,
+    pub samples: alloc::vec::Vec<i32>,
+    pub drift: alloc::collections::BTreeMap<u32, i64>,
+    pub valid: bool,
+    pub source: Option<reading::Source>,
+}
+impl Reading {
+    /// Returns the value of `sensor`, or the default value if `sensor` is unset.
+    pub fn sensor(&self) -> u32 {
+        match &self.sensor {
+            Some(value) => value.0,
+            None => 0,
+        }
+    }
+}
+/// Nested message and enum types in `Reading`.
+pub mod reading {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Source {
+        Channel(#[codec(compact)] u32),
+        Address(u32),
+        Correction(i64),
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Summary {
+    #[codec(compact)]
+    pub count: u32,
+    pub total: i64,
+}
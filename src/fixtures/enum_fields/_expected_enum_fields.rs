@@ -0,0 +1,104 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug)]
+pub struct Reading {
+    pub level: Level,
+    pub previous_level: Option<Level>,
+    pub history: alloc::vec::Vec<Level>,
+    pub levels_by_sensor: alloc::collections::BTreeMap<alloc::string::String, Level>,
+    pub unit: reading::Unit,
+    pub threshold: Option<reading::Threshold>,
+}
+impl Default for Reading {
+    fn default() -> Self {
+        Self {
+            level: Level::Unspecified,
+            previous_level: None,
+            history: Default::default(),
+            levels_by_sensor: Default::default(),
+            unit: reading::Unit::Celsius,
+            threshold: None,
+        }
+    }
+}
+impl Reading {
+    /// Returns the value of `previous_level`, or the default value if `previous_level` is unset.
+    pub fn previous_level(&self) -> &Level {
+        match &self.previous_level {
+            Some(value) => value,
+            None => &Level::Unspecified,
+        }
+    }
+}
+/// Nested message and enum types in `Reading`.
+pub mod reading {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Default)]
+    pub enum Unit {
+        #[codec(index = 0)]
+        #[default]
+        Celsius = 0,
+        #[codec(index = 1)]
+        Kelvin = 1,
+    }
+    impl Unit {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Celsius => "CELSIUS",
+                Self::Kelvin => "KELVIN",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> Option<Self> {
+            match value {
+                "CELSIUS" => Some(Self::Celsius),
+                "KELVIN" => Some(Self::Kelvin),
+                _ => None,
+            }
+        }
+    }
+    #[derive(Encode, Decode, Debug)]
+    pub enum Threshold {
+        LevelThreshold(super::Level),
+        RawThreshold(u32),
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub enum Level {
+    #[codec(index = 0)]
+    #[default]
+    Unspecified = 0,
+    #[codec(index = 1)]
+    Low = 1,
+    #[codec(index = 2)]
+    High = 2,
+}
+impl Level {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "LEVEL_UNSPECIFIED",
+            Self::Low => "LEVEL_LOW",
+            Self::High => "LEVEL_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "LEVEL_UNSPECIFIED" => Some(Self::Unspecified),
+            "LEVEL_LOW" => Some(Self::Low),
+            "LEVEL_HIGH" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
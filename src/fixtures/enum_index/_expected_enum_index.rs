@@ -0,0 +1,68 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub enum Priority {
+    #[codec(index = 0)]
+    #[default]
+    Low = 0,
+    #[codec(index = 255)]
+    Unknown = -1,
+    #[codec(index = 2)]
+    High = 1000,
+}
+impl Priority {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Low => "PRIORITY_LOW",
+            Self::Unknown => "PRIORITY_UNKNOWN",
+            Self::High => "PRIORITY_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "PRIORITY_LOW" => Some(Self::Low),
+            "PRIORITY_UNKNOWN" => Some(Self::Unknown),
+            "PRIORITY_HIGH" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub enum Sparse {
+    #[codec(index = 0)]
+    #[default]
+    None = 0,
+    #[codec(index = 5)]
+    Some = 5,
+    #[codec(index = 200)]
+    All = 200,
+}
+impl Sparse {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::None => "SPARSE_NONE",
+            Self::Some => "SPARSE_SOME",
+            Self::All => "SPARSE_ALL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "SPARSE_NONE" => Some(Self::None),
+            "SPARSE_SOME" => Some(Self::Some),
+            "SPARSE_ALL" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
@@ -2,7 +2,7 @@
 extern crate alloc;
 use parity_scale_codec::{Encode, Decode};
 
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct Container {
     pub data: Option<container::Data>,
 }
@@ -10,20 +10,20 @@ pub struct Container {
 pub mod container {
     use super::*;
 
-        #[derive(Encode, Decode)]
+    #[derive(Encode, Decode, Debug)]
     pub enum Data {
         Foo(alloc::boxed::Box<super::Foo>),
         Bar(super::Bar),
     }
 }
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct Foo {
     pub foo: alloc::string::String,
 }
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct Bar {
     pub qux: Option<alloc::boxed::Box<Qux>>,
 }
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct Qux {
 }
@@ -0,0 +1,21 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Record {
+    pub id: u32,
+    pub contact: Option<record::Contact>,
+    pub name: alloc::string::String,
+    pub active: bool,
+}
+/// Nested message and enum types in `Record`.
+pub mod record {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Contact {
+        Email(alloc::string::String),
+        Phone(alloc::string::String),
+    }
+}
@@ -0,0 +1,46 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Block {
+    /// The hash of the parent block.
+    pub parent_hash: alloc::vec::Vec<u8>,
+    pub signatures: alloc::vec::Vec<[u8; 64]>,
+    pub state_root: Option<[u8; 32]>,
+    pub extra_data: alloc::vec::Vec<u8>,
+    pub receipts: alloc::collections::BTreeMap<alloc::string::String, [u8; 32]>,
+    pub author: Option<block::Author>,
+}
+impl Block {
+    /// Returns the value of `state_root`, or the default value if `state_root` is unset.
+    pub fn state_root(&self) -> &[u8] {
+        match &self.state_root {
+            Some(value) => &value[..],
+            None => &[0; 32],
+        }
+    }
+}
+/// Nested message and enum types in `Block`.
+pub mod block {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Author {
+        PublicKey([u8; 32]),
+        Name(alloc::string::String),
+    }
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, parity_scale_codec::MaxEncodedLen)]
+pub struct Header {
+    pub hash: [u8; 32],
+    pub validators: bounded_collections::BoundedVec<[u8; 32], bounded_collections::ConstU32<100>>,
+}
+impl Default for Header {
+    fn default() -> Self {
+        Self {
+            hash: [0; 32],
+            validators: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Measurement {
+    pub ratio: F32Bits,
+    pub value: F64Bits,
+    pub error: Option<F64Bits>,
+    pub samples: alloc::vec::Vec<F32Bits>,
+    pub labels: alloc::collections::BTreeMap<alloc::string::String, F64Bits>,
+    pub reference: Option<measurement::Reference>,
+}
+impl Measurement {
+    /// Returns the value of `error`, or the default value if `error` is unset.
+    pub fn error(&self) -> f64 {
+        match &self.error {
+            Some(value) => value.get(),
+            None => 0f64,
+        }
+    }
+}
+/// Nested message and enum types in `Measurement`.
+pub mod measurement {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Reference {
+        Absolute(F64Bits),
+        Named(alloc::string::String),
+    }
+}
+
+/// A `f32` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct F32Bits(pub u32);
+
+impl F32Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f32 {
+        f32::from_bits(self.0)
+    }
+}
+
+impl From<f32> for F32Bits {
+    fn from(value: f32) -> Self {
+        F32Bits(value.to_bits())
+    }
+}
+
+impl From<F32Bits> for f32 {
+    fn from(value: F32Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F32Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+/// A `f64` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct F64Bits(pub u64);
+
+impl F64Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f64 {
+        f64::from_bits(self.0)
+    }
+}
+
+impl From<f64> for F64Bits {
+    fn from(value: f64) -> Self {
+        F64Bits(value.to_bits())
+    }
+}
+
+impl From<F64Bits> for f64 {
+    fn from(value: F64Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F64Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
@@ -0,0 +1,23 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Measurement {
+    pub ratio: ::float_codec::Float32,
+    pub value: ::float_codec::Float64,
+    pub error: Option<::float_codec::Float64>,
+    pub samples: alloc::vec::Vec<::float_codec::Float32>,
+    pub labels: alloc::collections::BTreeMap<alloc::string::String, ::float_codec::Float64>,
+    pub reference: Option<measurement::Reference>,
+}
+/// Nested message and enum types in `Measurement`.
+pub mod measurement {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Reference {
+        Absolute(::float_codec::Float64),
+        Named(alloc::string::String),
+    }
+}
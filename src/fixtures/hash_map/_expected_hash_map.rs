@@ -0,0 +1,69 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Ledger {
+    #[codec(encoded_as = "HashMapCodec<alloc::string::String, parity_scale_codec::Compact<u64>>")]
+    pub balances: std::collections::HashMap<alloc::string::String, parity_scale_codec::Compact<u64>>,
+    #[codec(encoded_as = "HashMapCodec<u32, ledger::Entry>")]
+    pub entries: std::collections::HashMap<u32, ledger::Entry>,
+    pub ordered: alloc::collections::BTreeMap<alloc::string::String, alloc::string::String>,
+}
+/// Nested message and enum types in `Ledger`.
+pub mod ledger {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Entry {
+        #[codec(encoded_as = "HashMapCodec<i32, bool>")]
+        pub flags: std::collections::HashMap<i32, bool>,
+    }
+}
+
+/// Encodes a hash map field like a `BTreeMap`, with the entries sorted by key so that the
+/// encoding is deterministic. Used through `#[codec(encoded_as = "..")]`.
+pub struct HashMapCodec<K, V>(pub alloc::vec::Vec<(K, V)>);
+
+/// The borrowed form of [`HashMapCodec`] used for encoding.
+pub struct HashMapCodecRef<'a, K, V>(alloc::vec::Vec<(&'a K, &'a V)>);
+
+impl<'a, K: Ord, V, S> From<&'a std::collections::HashMap<K, V, S>> for HashMapCodecRef<'a, K, V> {
+    fn from(map: &'a std::collections::HashMap<K, V, S>) -> Self {
+        let mut entries: alloc::vec::Vec<_> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        HashMapCodecRef(entries)
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for HashMapCodecRef<'_, K, V> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<'a, K: Ord + Encode + 'a, V: Encode + 'a, S: 'a> parity_scale_codec::EncodeAsRef<'a, std::collections::HashMap<K, V, S>>
+    for HashMapCodec<K, V>
+{
+    type RefType = HashMapCodecRef<'a, K, V>;
+}
+
+impl<K: Decode, V: Decode> Decode for HashMapCodec<K, V> {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        Ok(HashMapCodec(Decode::decode(input)?))
+    }
+}
+
+impl<K: Eq + core::hash::Hash, V, S: core::hash::BuildHasher + Default> From<HashMapCodec<K, V>>
+    for std::collections::HashMap<K, V, S>
+{
+    fn from(codec: HashMapCodec<K, V>) -> Self {
+        codec.0.into_iter().collect()
+    }
+}
@@ -0,0 +1,107 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, parity_scale_codec::MaxEncodedLen, Default)]
+pub struct Key {
+    pub public_key: bounded_collections::BoundedVec<u8, bounded_collections::ConstU32<32>>,
+    pub weight: ZigZagI32,
+}
+#[derive(Encode, Decode, Debug, parity_scale_codec::MaxEncodedLen, Default)]
+pub struct Account {
+    #[codec(compact)]
+    pub id: u64,
+    pub name: bounded_collections::BoundedVec<u8, bounded_collections::ConstU32<64>>,
+    pub primary: Option<Key>,
+    pub keys: bounded_collections::BoundedVec<Key, bounded_collections::ConstU32<8>>,
+    pub balances: bounded_collections::BoundedBTreeMap<u32, parity_scale_codec::Compact<u64>, bounded_collections::ConstU32<16>>,
+    pub history: bounded_collections::BoundedVec<parity_scale_codec::Compact<u64>, bounded_collections::ConstU32<100>>,
+    pub contact: Option<account::Contact>,
+}
+/// Nested message and enum types in `Account`.
+pub mod account {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, parity_scale_codec::MaxEncodedLen)]
+    pub enum Contact {
+        Email(bounded_collections::BoundedVec<u8, bounded_collections::ConstU32<128>>),
+        Extension(#[codec(compact)] u32),
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Audit {
+    pub note: alloc::string::String,
+    pub tags: alloc::vec::Vec<alloc::string::String>,
+    pub counters: alloc::collections::BTreeMap<alloc::string::String, parity_scale_codec::Compact<u32>>,
+    pub detail: Option<Unbounded>,
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Unbounded {
+    pub text: alloc::string::String,
+}
+#[derive(Encode, Decode, Debug, parity_scale_codec::MaxEncodedLen, Default)]
+pub enum Role {
+    #[codec(index = 0)]
+    #[default]
+    Member = 0,
+    #[codec(index = 1)]
+    Admin = 1,
+}
+impl Role {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Member => "ROLE_MEMBER",
+            Self::Admin => "ROLE_ADMIN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "ROLE_MEMBER" => Some(Self::Member),
+            "ROLE_ADMIN" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI32(pub i32);
+
+impl ZigZagI32 {
+    fn zigzag(self) -> u32 {
+        ((self.0 << 1) ^ (self.0 >> 31)) as u32
+    }
+}
+
+impl Encode for ZigZagI32 {
+    fn size_hint(&self) -> usize {
+        parity_scale_codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        parity_scale_codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl parity_scale_codec::EncodeLike for ZigZagI32 {}
+
+impl Decode for ZigZagI32 {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        let value = parity_scale_codec::Compact::<u32>::decode(input)?.0;
+        Ok(ZigZagI32((value >> 1) as i32 ^ -((value & 1) as i32)))
+    }
+}
+
+impl parity_scale_codec::MaxEncodedLen for ZigZagI32 {
+    fn max_encoded_len() -> usize {
+        parity_scale_codec::Compact::<u32>::max_encoded_len()
+    }
+}
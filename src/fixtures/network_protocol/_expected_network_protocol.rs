@@ -2,17 +2,17 @@
 extern crate alloc;
 use parity_scale_codec::{Encode, Decode};
 
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct Entity {
     pub id: alloc::string::String,
     pub ip_address: u32,
 }
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct AmountDetails {
     pub value: u64,
     pub is_locked: bool,
 }
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug)]
 pub struct TransactionRequest {
     pub is_priority: bool,
     pub transaction_id: u64,
@@ -24,21 +24,41 @@ pub struct TransactionRequest {
     pub status: i32,
     pub result: Option<transaction_request::Result>,
 }
+impl Default for TransactionRequest {
+    fn default() -> Self {
+        Self {
+            is_priority: Default::default(),
+            transaction_id: Default::default(),
+            creation_time: Default::default(),
+            memo: Default::default(),
+            associated_ids: Default::default(),
+            metadata: Default::default(),
+            sender: None,
+            status: 0,
+            result: None,
+        }
+    }
+}
 /// Nested message and enum types in `TransactionRequest`.
 pub mod transaction_request {
     use super::*;
 
-        #[derive(Encode, Decode)]
+    #[derive(Encode, Decode, Debug)]
     pub enum Result {
         Error(alloc::string::String),
         Amount(super::AmountDetails),
     }
 }
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub enum TransactionStatus {
+    #[codec(index = 0)]
+    #[default]
     StatusUnspecified = 0,
+    #[codec(index = 1)]
     StatusPending = 1,
+    #[codec(index = 2)]
     StatusConfirmed = 2,
+    #[codec(index = 3)]
     StatusRejected = 3,
 }
 impl TransactionStatus {
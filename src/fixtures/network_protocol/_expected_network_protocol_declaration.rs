@@ -0,0 +1,87 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Entity {
+    pub id: alloc::string::String,
+    pub ip_address: u32,
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct AmountDetails {
+    pub value: u64,
+    pub is_locked: bool,
+}
+#[derive(Encode, Decode, Debug)]
+pub struct TransactionRequest {
+    pub is_priority: bool,
+    pub transaction_id: u64,
+    pub creation_time: i64,
+    pub memo: alloc::string::String,
+    pub associated_ids: alloc::vec::Vec<alloc::string::String>,
+    pub metadata: alloc::collections::BTreeMap<alloc::string::String, u32>,
+    pub sender: Option<Entity>,
+    pub result: Option<transaction_request::Result>,
+    pub status: i32,
+}
+impl Default for TransactionRequest {
+    fn default() -> Self {
+        Self {
+            is_priority: Default::default(),
+            transaction_id: Default::default(),
+            creation_time: Default::default(),
+            memo: Default::default(),
+            associated_ids: Default::default(),
+            metadata: Default::default(),
+            sender: None,
+            result: None,
+            status: 0,
+        }
+    }
+}
+/// Nested message and enum types in `TransactionRequest`.
+pub mod transaction_request {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Result {
+        Error(alloc::string::String),
+        Amount(super::AmountDetails),
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub enum TransactionStatus {
+    #[codec(index = 0)]
+    #[default]
+    StatusUnspecified = 0,
+    #[codec(index = 1)]
+    StatusPending = 1,
+    #[codec(index = 2)]
+    StatusConfirmed = 2,
+    #[codec(index = 3)]
+    StatusRejected = 3,
+}
+impl TransactionStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::StatusUnspecified => "STATUS_UNSPECIFIED",
+            Self::StatusPending => "STATUS_PENDING",
+            Self::StatusConfirmed => "STATUS_CONFIRMED",
+            Self::StatusRejected => "STATUS_REJECTED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "STATUS_UNSPECIFIED" => Some(Self::StatusUnspecified),
+            "STATUS_PENDING" => Some(Self::StatusPending),
+            "STATUS_CONFIRMED" => Some(Self::StatusConfirmed),
+            "STATUS_REJECTED" => Some(Self::StatusRejected),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Hash))]
+pub struct Account {
+    pub name: alloc::string::String,
+    pub history: bounded_collections::BoundedVec<u64, bounded_collections::ConstU32<16>>,
+    pub key: alloc::vec::Vec<u8>,
+    pub labels: alloc::collections::BTreeMap<alloc::string::String, u32>,
+    pub parent: Option<alloc::boxed::Box<Account>>,
+    pub contact: Option<account::Contact>,
+}
+/// Nested message and enum types in `Account`.
+pub mod account {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(Hash))]
+    pub enum Contact {
+        Email(alloc::string::String),
+        Delegate(alloc::boxed::Box<super::Account>),
+    }
+}
@@ -0,0 +1,22 @@
+// This file is @generated by ppsc-build.
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Account {
+    pub name: std::string::String,
+    pub history: bounded_collections::BoundedVec<u64, bounded_collections::ConstU32<16>>,
+    pub key: std::vec::Vec<u8>,
+    pub labels: std::collections::BTreeMap<std::string::String, u32>,
+    pub parent: Option<std::boxed::Box<Account>>,
+    pub contact: Option<account::Contact>,
+}
+/// Nested message and enum types in `Account`.
+pub mod account {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Contact {
+        Email(std::string::String),
+        Delegate(std::boxed::Box<super::Account>),
+    }
+}
@@ -0,0 +1,36 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Payment {
+    pub method: Option<payment::Method>,
+}
+/// Nested message and enum types in `Payment`.
+pub mod payment {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Method {
+        #[codec(index = 3)]
+        CardToken(alloc::string::String),
+        #[codec(index = 1)]
+        AccountId(u64),
+        #[codec(index = 7)]
+        Cash(bool),
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Wide {
+    pub choice: Option<wide::Choice>,
+}
+/// Nested message and enum types in `Wide`.
+pub mod wide {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug)]
+    pub enum Choice {
+        Small(bool),
+        Large(bool),
+    }
+}
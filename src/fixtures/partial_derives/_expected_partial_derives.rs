@@ -0,0 +1,100 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Default)]
+pub struct Inner {
+    pub x: u32,
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq)]
+pub struct Outer {
+    pub inner: Option<Inner>,
+    pub kind: Kind,
+    pub choice: Option<outer::Choice>,
+}
+impl Default for Outer {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            kind: Kind::Unspecified,
+            choice: None,
+        }
+    }
+}
+/// Nested message and enum types in `Outer`.
+pub mod outer {
+    use super::*;
+
+    #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Choice {
+        Number(u32),
+        Text(alloc::string::String),
+    }
+}
+#[derive(Encode, Decode, Debug)]
+pub struct Leveled {
+    pub level: Level,
+}
+impl Default for Leveled {
+    fn default() -> Self {
+        Self {
+            level: Level::Unspecified,
+        }
+    }
+}
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Kind {
+    #[codec(index = 0)]
+    #[default]
+    Unspecified = 0,
+    #[codec(index = 1)]
+    Leaf = 1,
+}
+impl Kind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "KIND_UNSPECIFIED",
+            Self::Leaf => "KIND_LEAF",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "KIND_LEAF" => Some(Self::Leaf),
+            _ => None,
+        }
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub enum Level {
+    #[codec(index = 0)]
+    #[default]
+    Unspecified = 0,
+    #[codec(index = 1)]
+    High = 1,
+}
+impl Level {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "LEVEL_UNSPECIFIED",
+            Self::High => "LEVEL_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "LEVEL_UNSPECIFIED" => Some(Self::Unspecified),
+            "LEVEL_HIGH" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
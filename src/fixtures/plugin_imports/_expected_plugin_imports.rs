@@ -0,0 +1,18 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Sample {
+    pub level: Option<i32>,
+    pub point: Option<super::common::Point>,
+}
+impl Sample {
+    /// Returns the value of `level`, or the default value if `level` is unset.
+    pub fn level(&self) -> i32 {
+        match &self.level {
+            Some(value) => *value,
+            None => 2,
+        }
+    }
+}
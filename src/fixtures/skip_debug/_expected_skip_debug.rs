@@ -0,0 +1,57 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+
+#[derive(Encode, Decode, Default)]
+pub struct Account {
+    pub id: u64,
+    pub name: alloc::string::String,
+    pub private_key: alloc::vec::Vec<u8>,
+    pub credential: Option<account::Credential>,
+}
+impl core::fmt::Debug for Account {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Account")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("private_key", &format_args!("<redacted>"))
+            .field("credential", &self.credential)
+            .finish()
+    }
+}
+/// Nested message and enum types in `Account`.
+pub mod account {
+    use super::*;
+
+    #[derive(Encode, Decode)]
+    pub enum Credential {
+        Password(alloc::string::String),
+        Username(alloc::string::String),
+    }
+    impl core::fmt::Debug for Credential {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Password(_) => f.debug_tuple("Password").field(&format_args!("<redacted>")).finish(),
+                Self::Username(value) => f.debug_tuple("Username").field(value).finish(),
+            }
+        }
+    }
+}
+#[derive(Encode, Decode, Debug, Default)]
+pub struct Session {
+    pub token: alloc::string::String,
+    pub expires_at: u64,
+}
+#[derive(Encode, Decode, Default)]
+pub struct Vault {
+    pub r#type: alloc::string::String,
+    pub secrets: alloc::vec::Vec<alloc::vec::Vec<u8>>,
+}
+impl core::fmt::Debug for Vault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Vault")
+            .field("type", &format_args!("<redacted>"))
+            .field("secrets", &format_args!("<redacted>"))
+            .finish()
+    }
+}
@@ -2,7 +2,7 @@
 extern crate alloc;
 use parity_scale_codec::{Encode, Decode};
 
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, Debug, Default)]
 pub struct Person {
     pub name: alloc::string::String,
     pub id: i32,
@@ -13,15 +13,27 @@ pub struct Person {
 pub mod person {
     use super::*;
 
-    #[derive(Encode, Decode)]
+    #[derive(Encode, Decode, Debug)]
     pub struct PhoneNumber {
         pub number: alloc::string::String,
         pub r#type: i32,
     }
-    #[derive(Encode, Decode)]
+    impl Default for PhoneNumber {
+        fn default() -> Self {
+            Self {
+                number: Default::default(),
+                r#type: 0,
+            }
+        }
+    }
+    #[derive(Encode, Decode, Debug, Default)]
     pub enum PhoneType {
+        #[codec(index = 0)]
+        #[default]
         Mobile = 0,
+        #[codec(index = 1)]
         Home = 1,
+        #[codec(index = 2)]
         Work = 2,
     }
     impl PhoneType {
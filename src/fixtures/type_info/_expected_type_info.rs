@@ -0,0 +1,143 @@
+// This file is @generated by ppsc-build.
+extern crate alloc;
+use parity_scale_codec::{Encode, Decode};
+use scale_info::TypeInfo;
+
+/// A transfer between two accounts.
+#[derive(Encode, Decode, Debug, TypeInfo, Default)]
+#[scale_info(capture_docs = "always")]
+pub struct Transfer {
+    /// The sending account.
+    pub from: alloc::string::String,
+    /// The receiving account.
+    pub to: alloc::string::String,
+    /// The amount, in the smallest unit.
+    #[codec(compact)]
+    pub amount: u64,
+    /// Balance adjustment applied to the sender.
+    pub adjustment: ZigZagI64,
+    /// Exchange rate at the time of the transfer.
+    pub rate: F64Bits,
+    /// Optional memo attached to the transfer.
+    pub memo: Option<transfer::Memo>,
+}
+/// Nested message and enum types in `Transfer`.
+pub mod transfer {
+    use super::*;
+
+    /// Optional memo attached to the transfer.
+    #[derive(Encode, Decode, Debug, TypeInfo)]
+    #[scale_info(capture_docs = "always")]
+    pub enum Memo {
+        /// Free form text.
+        Text(alloc::string::String),
+        /// Reference to an invoice.
+        Invoice(#[codec(compact)] u32),
+    }
+}
+/// The state of a transfer.
+#[derive(Encode, Decode, Debug, TypeInfo, Default)]
+#[scale_info(capture_docs = "always")]
+pub enum Status {
+    /// The transfer has not been processed.
+    #[codec(index = 0)]
+    #[default]
+    Pending = 0,
+    /// The transfer was applied.
+    #[codec(index = 1)]
+    Applied = 1,
+}
+impl Status {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Pending => "STATUS_PENDING",
+            Self::Applied => "STATUS_APPLIED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "STATUS_PENDING" => Some(Self::Pending),
+            "STATUS_APPLIED" => Some(Self::Applied),
+            _ => None,
+        }
+    }
+}
+
+/// A signed integer encoded as a SCALE compact integer after zigzag mapping, so that values
+/// close to zero encode in few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZagI64(pub i64);
+
+impl ZigZagI64 {
+    fn zigzag(self) -> u64 {
+        ((self.0 << 1) ^ (self.0 >> 63)) as u64
+    }
+}
+
+impl Encode for ZigZagI64 {
+    fn size_hint(&self) -> usize {
+        parity_scale_codec::Compact(self.zigzag()).size_hint()
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        parity_scale_codec::Compact(self.zigzag()).encode_to(dest)
+    }
+}
+
+impl parity_scale_codec::EncodeLike for ZigZagI64 {}
+
+impl Decode for ZigZagI64 {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, parity_scale_codec::Error> {
+        let value = parity_scale_codec::Compact::<u64>::decode(input)?.0;
+        Ok(ZigZagI64((value >> 1) as i64 ^ -((value & 1) as i64)))
+    }
+}
+
+impl scale_info::TypeInfo for ZigZagI64 {
+    type Identity = Self;
+
+    fn type_info() -> scale_info::Type {
+        scale_info::Type::builder()
+            .path(scale_info::Path::new("ZigZagI64", module_path!()))
+            .composite(
+                scale_info::build::Fields::unnamed()
+                    .field(|f| f.compact::<u64>().type_name("u64")),
+            )
+    }
+}
+
+/// A `f64` stored as its IEEE 754 bit pattern, since SCALE has no floating point encoding.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Encode, Decode, TypeInfo)]
+pub struct F64Bits(pub u64);
+
+impl F64Bits {
+    /// Returns the floating point value.
+    pub fn get(self) -> f64 {
+        f64::from_bits(self.0)
+    }
+}
+
+impl From<f64> for F64Bits {
+    fn from(value: f64) -> Self {
+        F64Bits(value.to_bits())
+    }
+}
+
+impl From<F64Bits> for f64 {
+    fn from(value: F64Bits) -> Self {
+        value.get()
+    }
+}
+
+impl core::fmt::Debug for F64Bits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.get(), f)
+    }
+}
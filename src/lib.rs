@@ -7,18 +7,40 @@ mod ast;
 pub use crate::ast::{Comments, Method, Service};
 
 mod collections;
+pub use collections::NoStdMode;
 pub(crate) use collections::{BytesType, MapType};
 
 mod code_generator;
 mod context;
+
+mod derives;
+pub use derives::STANDARD_DERIVES;
+
+mod encoding;
+pub use encoding::{
+    EncodingProfile, EnumIndex, FieldOrder, FloatStrategy, OneofIndex, SignedVarint,
+};
+
+mod error;
+pub use error::{Error, Location, Span};
+
 mod extern_paths;
+mod features;
 mod ident;
+
+mod manifest;
+pub use manifest::{Manifest, ManifestFile};
+
 mod message_graph;
 mod path;
 
 mod config;
 pub use config::Config;
 
+mod options;
+pub use options::BOOLEAN_OPTIONS;
+mod plugin;
+
 mod module;
 pub use module::Module;
 
@@ -393,6 +415,1384 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_typed_enums() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .typed_enums(["."])
+            .compile_protos(
+                &["src/fixtures/enum_fields/enum_fields.proto"],
+                &["src/fixtures/enum_fields"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/enum_fields/_expected_enum_fields.rs",
+            tempdir.path().join("enum_fields.rs")
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .compact(["."])
+            .compile_protos(
+                &["src/fixtures/compact/compact.proto"],
+                &["src/fixtures/compact"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/compact/_expected_compact.rs",
+            tempdir.path().join("compact.rs")
+        );
+    }
+
+    #[test]
+    fn test_compact_signed_field() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .compact([".compact.Transfer.delta"])
+            .compile_protos(
+                &["src/fixtures/compact/compact.proto"],
+                &["src/fixtures/compact"],
+            )
+            .unwrap_err();
+
+        let Some(Error::UnsupportedType { location, message }) = Error::from_io_error(&error)
+        else {
+            panic!("expected an unsupported type error, got {:?}", error);
+        };
+        assert_eq!(location.path, ".compact.Transfer.delta");
+        assert_eq!(
+            message,
+            "field `.compact.Transfer.delta`: signed integers have no SCALE compact encoding; remove the field from `Config::compact`"
+        );
+    }
+
+    #[test]
+    fn test_encoding_profile() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .compile_protos(
+                &["src/fixtures/encoding_profile/encoding_profile.proto"],
+                &["src/fixtures/encoding_profile"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/encoding_profile/_expected_encoding_profile.rs",
+            tempdir.path().join("encoding_profile.rs")
+        );
+    }
+
+    #[test]
+    fn test_encoding_profile_fixed_width_signed() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .signed_varint(SignedVarint::FixedWidth)
+            .compile_protos(
+                &["src/fixtures/encoding_profile/encoding_profile.proto"],
+                &["src/fixtures/encoding_profile"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/encoding_profile/_expected_encoding_profile_fixed_width_signed.rs",
+            tempdir.path().join("encoding_profile.rs")
+        );
+    }
+
+    #[test]
+    fn test_encoding_profile_reject_signed() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .signed_varint(SignedVarint::Reject)
+            .compile_protos(
+                &["src/fixtures/encoding_profile/encoding_profile.proto"],
+                &["src/fixtures/encoding_profile"],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(
+            error
+                .to_string()
+                .contains("field `.encoding_profile.Reading.offset`"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_float_bit_cast() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .compile_protos(&["src/fixtures/float/float.proto"], &["src/fixtures/float"])
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/float/_expected_float.rs",
+            tempdir.path().join("float.rs")
+        );
+    }
+
+    #[test]
+    fn test_float_wrapper() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .float_strategy(FloatStrategy::Wrapper {
+                f32: "::float_codec::Float32".to_string(),
+                f64: "::float_codec::Float64".to_string(),
+            })
+            .compile_protos(&["src/fixtures/float/float.proto"], &["src/fixtures/float"])
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/float/_expected_float_wrapper.rs",
+            tempdir.path().join("float.rs")
+        );
+    }
+
+    #[test]
+    fn test_float_reject() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .float_strategy(FloatStrategy::Reject)
+            .compile_protos(&["src/fixtures/float/float.proto"], &["src/fixtures/float"])
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        let message = error.to_string();
+        for field in ["ratio", "value", "error", "samples", "labels", "absolute"] {
+            assert!(
+                message.contains(&format!("`.float.Measurement.{}`", field)),
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_derives() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .derives(["."], STANDARD_DERIVES)
+            .derives([".derives.Custom"], ["Encode", "Decode", "Clone"])
+            .compact(["values"])
+            .compile_protos(
+                &["src/fixtures/derives/derives.proto"],
+                &["src/fixtures/derives"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/derives/_expected_derives.rs",
+            tempdir.path().join("derives.rs")
+        );
+    }
+
+    #[test]
+    fn test_partial_derives() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .typed_enums(["."])
+            .derives([".partial_derives.Outer"], STANDARD_DERIVES)
+            .derives(
+                [".partial_derives.Inner"],
+                ["Encode", "Decode", "Debug", "Clone", "PartialEq"],
+            )
+            .derives([".partial_derives.Kind"], STANDARD_DERIVES)
+            .derives([".partial_derives.Leveled"], STANDARD_DERIVES)
+            .compile_protos(
+                &["src/fixtures/partial_derives/partial_derives.proto"],
+                &["src/fixtures/partial_derives"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/partial_derives/_expected_partial_derives.rs",
+            tempdir.path().join("partial_derives.rs")
+        );
+    }
+
+    mod partial_derives {
+        include!("fixtures/partial_derives/_expected_partial_derives.rs");
+    }
+
+    #[test]
+    fn test_partial_derives_compile() {
+        use partial_derives::{Inner, Kind, Level, Leveled, Outer, outer::Choice};
+
+        // `Outer` only derives what `Inner` derives, and its oneof everything configured.
+        let outer = Outer {
+            inner: Some(Inner { x: 1 }),
+            kind: Kind::Leaf,
+            choice: Some(Choice::Number(2)),
+        };
+        assert_eq!(outer.clone(), outer);
+        let mut choices = std::collections::HashSet::new();
+        choices.insert(Choice::Text(String::from("text")));
+        assert_eq!(choices.len(), 1);
+        assert_eq!(
+            Kind::from_str_name(Kind::Leaf.as_str_name()),
+            Some(Kind::Leaf)
+        );
+
+        // `Leveled` keeps the default derives of its enum.
+        let leveled = Leveled { level: Level::High };
+        assert_eq!(
+            Level::from_str_name(leveled.level.as_str_name()).map(|level| level as i32),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_derives_float_wrapper() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .derives(["."], STANDARD_DERIVES)
+            .float_strategy(FloatStrategy::Wrapper {
+                f32: String::from("crate::Float32"),
+                f64: String::from("crate::Float64"),
+            })
+            .compile_protos(
+                &["src/fixtures/derives/derives.proto"],
+                &["src/fixtures/derives"],
+            )
+            .unwrap();
+
+        // Unlike `F64Bits`, the wrapper types might not implement `Eq` and `Hash`.
+        let generated = std::fs::read_to_string(tempdir.path().join("derives.rs")).unwrap();
+        assert!(generated.contains(
+            "#[derive(Encode, Decode, Debug, Clone, PartialEq, Default)]\npub struct Position {"
+        ));
+        assert!(generated.contains(
+            "#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, Hash)]\npub struct Plain {"
+        ));
+    }
+
+    #[test]
+    fn test_skip_debug() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .skip_debug([
+                ".skip_debug.Account.private_key",
+                "password",
+                ".skip_debug.Vault",
+            ])
+            .compile_protos(
+                &["src/fixtures/skip_debug/skip_debug.proto"],
+                &["src/fixtures/skip_debug"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/skip_debug/_expected_skip_debug.rs",
+            tempdir.path().join("skip_debug.rs")
+        );
+    }
+
+    #[test]
+    fn test_type_info() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .type_info(true)
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .compile_protos(
+                &["src/fixtures/type_info/type_info.proto"],
+                &["src/fixtures/type_info"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/type_info/_expected_type_info.rs",
+            tempdir.path().join("type_info.rs")
+        );
+    }
+
+    #[test]
+    fn test_max_encoded_len() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .max_encoded_len([
+                ".max_encoded_len.Role",
+                ".max_encoded_len.Key",
+                ".max_encoded_len.Account",
+            ])
+            .max_len(".max_encoded_len.Key.public_key", 32)
+            .max_len(".max_encoded_len.Account.name", 64)
+            .max_len(".max_encoded_len.Account.keys", 8)
+            .max_len(".max_encoded_len.Account.balances", 16)
+            .max_len(".max_encoded_len.Account.history", 100)
+            .max_len("email", 128)
+            .compile_protos(
+                &["src/fixtures/max_encoded_len/max_encoded_len.proto"],
+                &["src/fixtures/max_encoded_len"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/max_encoded_len/_expected_max_encoded_len.rs",
+            tempdir.path().join("max_encoded_len.rs")
+        );
+    }
+
+    #[test]
+    fn test_bytes_type() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .derives(["."], STANDARD_DERIVES)
+            .bytes(["."])
+            .compile_protos(
+                &["src/fixtures/bytes_type/bytes_type.proto"],
+                &["src/fixtures/bytes_type"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/bytes_type/_expected_bytes_type.rs",
+            tempdir.path().join("bytes_type.rs")
+        );
+    }
+
+    mod bytes_type {
+        include!("fixtures/bytes_type/_expected_bytes_type.rs");
+    }
+
+    #[test]
+    fn test_bytes_type_roundtrip() {
+        use parity_scale_codec::{Decode, Encode};
+
+        let blob = bytes_type::Blob {
+            data: bytes::Bytes::from_static(b"data"),
+            checksum: Some(bytes::Bytes::from_static(&[0xab; 4])),
+            chunks: vec![bytes::Bytes::from_static(b"a"), bytes::Bytes::new()],
+            metadata: [(String::from("key"), bytes::Bytes::from_static(b"value"))].into(),
+            source: Some(bytes_type::blob::Source::Inline(bytes::Bytes::from_static(
+                b"inline",
+            ))),
+        };
+        let encoded = blob.encode();
+        assert_eq!(
+            bytes_type::Blob::decode(&mut encoded.as_slice()).unwrap(),
+            blob
+        );
+        assert_eq!(blob.checksum(), &[0xab; 4]);
+    }
+
+    #[test]
+    fn test_editions() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .typed_enums(["."])
+            .compile_encoded_fds(include_bytes!("fixtures/editions/editions.bin"))
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/editions/_expected_editions.rs",
+            tempdir.path().join("editions.rs")
+        );
+    }
+
+    #[test]
+    fn test_editions_without_features() {
+        use prost::Message;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let fds = FileDescriptorSet::decode(&include_bytes!("fixtures/editions/editions.bin")[..])
+            .unwrap();
+
+        // The decoded descriptors lost the edition and features of the file.
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .compile_fds(fds)
+            .unwrap_err();
+
+        let Some(Error::InvalidSyntax { location, message }) = Error::from_io_error(&error) else {
+            panic!("expected an invalid syntax error, got {:?}", error);
+        };
+        assert_eq!(location.file, "editions.proto");
+        assert_eq!(
+            message,
+            "file `editions.proto`: the edition and features of Editions files are not retained by `FileDescriptorSet`; compile the encoded descriptors with `Config::compile_encoded_fds`"
+        );
+    }
+
+    #[test]
+    fn test_unknown_syntax() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let fds = FileDescriptorSet {
+            file: vec![prost_types::FileDescriptorProto {
+                name: Some(String::from("unknown.proto")),
+                package: Some(String::from("unknown")),
+                syntax: Some(String::from("proto4")),
+                ..Default::default()
+            }],
+        };
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .compile_fds(fds)
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "file `unknown.proto`: unknown syntax `proto4`\n --> unknown.proto"
+        );
+        assert_eq!(
+            Error::from_io_error(&error),
+            Some(&Error::InvalidSyntax {
+                location: Location {
+                    file: String::from("unknown.proto"),
+                    path: String::from(".unknown"),
+                    span: None,
+                    snippet: None,
+                },
+                message: String::from("file `unknown.proto`: unknown syntax `proto4`"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_enum_variant_name_collision() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let value = |name: &str, number| prost_types::EnumValueDescriptorProto {
+            name: Some(String::from(name)),
+            number: Some(number),
+            ..Default::default()
+        };
+        let fds = FileDescriptorSet {
+            file: vec![prost_types::FileDescriptorProto {
+                name: Some(String::from("collision.proto")),
+                package: Some(String::from("collision")),
+                syntax: Some(String::from("proto3")),
+                enum_type: vec![prost_types::EnumDescriptorProto {
+                    name: Some(String::from("Color")),
+                    value: vec![value("COLOR_RED", 0), value("RED", 1)],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .compile_fds(fds)
+            .unwrap_err();
+
+        let Some(Error::NameCollision { location, message }) = Error::from_io_error(&error) else {
+            panic!("expected a name collision, got {:?}", error);
+        };
+        assert_eq!(location.file, "collision.proto");
+        assert_eq!(location.path, ".collision.Color");
+        assert_eq!(
+            message,
+            "enum `.collision.Color`: generated variant names overlap: `Red` would be used by both `COLOR_RED` and `RED`"
+        );
+    }
+
+    #[test]
+    fn test_hash_map() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .derives(["."], STANDARD_DERIVES)
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .hash_map(["."])
+            .hash_map_path("std::collections::HashMap")
+            .btree_map([".hash_map.Ledger.ordered"])
+            .compile_protos(
+                &["src/fixtures/hash_map/hash_map.proto"],
+                &["src/fixtures/hash_map"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/hash_map/_expected_hash_map.rs",
+            tempdir.path().join("hash_map.rs")
+        );
+    }
+
+    #[test]
+    fn test_hash_map_no_std() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .hash_map([".hash_map.Ledger.balances"])
+            .compile_protos(
+                &["src/fixtures/hash_map/hash_map.proto"],
+                &["src/fixtures/hash_map"],
+            )
+            .unwrap_err();
+
+        let Some(Error::UnsupportedType { location, message }) = Error::from_io_error(&error)
+        else {
+            panic!("expected an unsupported type error, got {:?}", error);
+        };
+        assert_eq!(location.path, ".hash_map.Ledger.balances");
+        assert_eq!(
+            message,
+            "field `.hash_map.Ledger.balances`: `std::collections::HashMap` is not available without `std`; use `Config::hash_map_path` to choose a hash map type, or `Config::btree_map` for this field"
+        );
+    }
+
+    mod hash_map {
+        include!("fixtures/hash_map/_expected_hash_map.rs");
+    }
+
+    #[test]
+    fn test_hash_map_encodes_like_btree_map() {
+        use parity_scale_codec::{Compact, Decode, Encode};
+        use std::collections::BTreeMap;
+
+        let balances = [("carol", 3), ("alice", 1), ("bob", 2), ("dave", 4)]
+            .map(|(name, balance)| (String::from(name), Compact(balance)));
+        let ledger = hash_map::Ledger {
+            balances: balances.clone().into(),
+            entries: [(
+                7,
+                hash_map::ledger::Entry {
+                    flags: Default::default(),
+                },
+            )]
+            .into(),
+            ordered: BTreeMap::new(),
+        };
+        let encoded = ledger.encode();
+        let expected = (
+            BTreeMap::from(balances),
+            BTreeMap::from([(7u32, BTreeMap::<i32, bool>::new())]),
+            BTreeMap::<String, String>::new(),
+        )
+            .encode();
+        assert_eq!(encoded, expected);
+        assert_eq!(
+            hash_map::Ledger::decode(&mut encoded.as_slice()).unwrap(),
+            ledger
+        );
+    }
+
+    #[test]
+    fn test_hash_map_type_info() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .type_info(true)
+            .hash_map([".hash_map.Ledger.balances"])
+            .compile_protos(
+                &["src/fixtures/hash_map/hash_map.proto"],
+                &["src/fixtures/hash_map"],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "field `.hash_map.Ledger.balances`: hash maps do not implement `TypeInfo`; use `Config::btree_map` for this field
+ --> hash_map.proto:6:3
+  |
+6 |   map<string, uint64> balances = 1;
+  |   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"
+        );
+        let Some(Error::UnsupportedType { location, .. }) = Error::from_io_error(&error) else {
+            panic!("expected an unsupported type, got {:?}", error);
+        };
+        assert_eq!(
+            location,
+            &Location {
+                file: String::from("hash_map.proto"),
+                path: String::from(".hash_map.Ledger.balances"),
+                span: Some(Span {
+                    start_line: 5,
+                    start_column: 2,
+                    end_line: 5,
+                    end_column: 35,
+                }),
+                snippet: Some(String::from("  map<string, uint64> balances = 1;")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_plugin_response() {
+        use prost::Message;
+        use prost_types::compiler::{CodeGeneratorRequest, CodeGeneratorResponse};
+
+        let fds = protox::compile(["compact.proto"], ["src/fixtures/compact"]).unwrap();
+        let request = CodeGeneratorRequest {
+            file_to_generate: vec![String::from("compact.proto")],
+            parameter: Some(String::from("compact=.")),
+            proto_file: fds.file,
+            compiler_version: None,
+        };
+
+        let response = Config::new().plugin_response(&request.encode_to_vec());
+        let response = CodeGeneratorResponse::decode(&response[..]).unwrap();
+
+        assert_eq!(response.error, None);
+        assert_eq!(response.supported_features, Some(3));
+        let names: Vec<&str> = response.file.iter().map(|file| file.name()).collect();
+        assert_eq!(names, ["compact.rs"]);
+        assert_eq_fixture_contents!(
+            "src/fixtures/compact/_expected_compact.rs",
+            response.file[0].content()
+        );
+
+        let request = CodeGeneratorRequest {
+            parameter: Some(String::from("compacts=.")),
+            ..request
+        };
+        let response = Config::new().plugin_response(&request.encode_to_vec());
+        let response = CodeGeneratorResponse::decode(&response[..]).unwrap();
+        assert_eq!(response.error.as_deref(), Some("unknown option `compacts`"));
+        assert!(response.file.is_empty());
+    }
+
+    #[test]
+    fn test_plugin_response_imports() {
+        use prost::Message;
+        use prost_types::compiler::{CodeGeneratorRequest, CodeGeneratorResponse};
+
+        let fds =
+            protox::compile(["plugin_imports.proto"], ["src/fixtures/plugin_imports"]).unwrap();
+        let request = CodeGeneratorRequest {
+            file_to_generate: vec![String::from("plugin_imports.proto")],
+            parameter: Some(String::from(
+                "derives=.=Encode+Decode+Debug+Clone+PartialEq+Eq+Hash",
+            )),
+            proto_file: fds.file,
+            compiler_version: None,
+        };
+
+        let response = Config::new().plugin_response(&request.encode_to_vec());
+        let response = CodeGeneratorResponse::decode(&response[..]).unwrap();
+
+        // The imported file is only generated when it is requested, but its types are known.
+        assert_eq!(response.error, None);
+        let names: Vec<&str> = response.file.iter().map(|file| file.name()).collect();
+        assert_eq!(names, ["plugin_imports.rs"]);
+        assert_eq_fixture_contents!(
+            "src/fixtures/plugin_imports/_expected_plugin_imports.rs",
+            response.file[0].content()
+        );
+    }
+
+    #[test]
+    fn test_check_protos() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut config = Config::new();
+        config
+            .out_dir(tempdir.path())
+            .include_file("mod.rs")
+            .compact(["."]);
+        let protos = ["src/fixtures/compact/compact.proto"];
+        let includes = ["src/fixtures/compact"];
+
+        let stale = config.check_protos(&protos, &includes).unwrap();
+        assert_eq!(
+            stale,
+            [
+                tempdir.path().join("compact.rs"),
+                tempdir.path().join("mod.rs"),
+                tempdir.path().join(Manifest::FILE_NAME),
+            ]
+        );
+        assert!(!tempdir.path().join("compact.rs").exists());
+
+        config.compile_protos(&protos, &includes).unwrap();
+        assert!(config.check_protos(&protos, &includes).unwrap().is_empty());
+
+        std::fs::write(tempdir.path().join("compact.rs"), "// edited\n").unwrap();
+        assert_eq!(
+            config.check_protos(&protos, &includes).unwrap(),
+            [tempdir.path().join("compact.rs")]
+        );
+    }
+
+    #[test]
+    fn test_remove_stale_outputs() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let protos = tempdir.path().join("protos");
+        let out_dir = tempdir.path().join("generated");
+        std::fs::create_dir_all(&protos).unwrap();
+        std::fs::create_dir_all(&out_dir).unwrap();
+        for package in ["a", "b", "c"] {
+            std::fs::write(
+                protos.join(format!("{}.proto", package)),
+                format!(
+                    "syntax = \"proto3\";\npackage {};\nmessage M {{ uint32 id = 1; }}\n",
+                    package
+                ),
+            )
+            .unwrap();
+        }
+
+        let mut config = Config::new();
+        config.out_dir(&out_dir);
+        config
+            .compile_protos(&["a.proto", "b.proto", "c.proto"], &[&protos])
+            .unwrap();
+        let manifest = Manifest::read(&out_dir).unwrap();
+        let names: Vec<&str> = manifest
+            .files()
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect();
+        assert_eq!(names, ["a.rs", "b.rs", "c.rs"]);
+        assert_eq!(
+            manifest.get("a.rs").map(|file| file.hash),
+            Some(Manifest::content_hash(
+                &std::fs::read(out_dir.join("a.rs")).unwrap()
+            ))
+        );
+
+        // `c.rs` was edited since it was generated, so it is kept.
+        std::fs::write(out_dir.join("c.rs"), "// edited\n").unwrap();
+        assert_eq!(
+            config.check_protos(&["a.proto"], &[&protos]).unwrap(),
+            [
+                out_dir.join(Manifest::FILE_NAME),
+                out_dir.join("b.rs"),
+                out_dir.join("c.rs"),
+            ]
+        );
+
+        config.compile_protos(&["a.proto"], &[&protos]).unwrap();
+        assert!(out_dir.join("a.rs").exists());
+        assert!(!out_dir.join("b.rs").exists());
+        assert!(out_dir.join("c.rs").exists());
+        // The kept file stays listed, and is reported until it is removed.
+        let manifest = Manifest::read(&out_dir).unwrap();
+        let names: Vec<&str> = manifest
+            .files()
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect();
+        assert_eq!(names, ["a.rs", "c.rs"]);
+        assert_eq!(
+            config.check_protos(&["a.proto"], &[&protos]).unwrap(),
+            [out_dir.join("c.rs")]
+        );
+
+        std::fs::remove_file(out_dir.join("c.rs")).unwrap();
+        config.compile_protos(&["a.proto"], &[&protos]).unwrap();
+        assert_eq!(Manifest::read(&out_dir).unwrap().files().len(), 1);
+        assert!(
+            config
+                .check_protos(&["a.proto"], &[&protos])
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_manifest_include_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let out_dir = tempdir.path().join("generated");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let protos = ["src/fixtures/compact/compact.proto"];
+        let includes = ["src/fixtures/compact"];
+
+        // An include file within the output directory is listed relative to it.
+        let mut config = Config::new();
+        config
+            .out_dir(&out_dir)
+            .include_file(out_dir.join("mod.rs"));
+        config.compile_protos(&protos, &includes).unwrap();
+        let manifest = Manifest::read(&out_dir).unwrap();
+        let names: Vec<&str> = manifest
+            .files()
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect();
+        assert_eq!(names, ["compact.rs", "mod.rs"]);
+        config.compile_protos(&protos, &includes).unwrap();
+        assert!(out_dir.join("mod.rs").exists());
+        assert!(config.check_protos(&protos, &includes).unwrap().is_empty());
+
+        // An include file outside of it is left out of the manifest.
+        let include_file = tempdir.path().join("protos.rs");
+        let mut config = Config::new();
+        config.out_dir(&out_dir).include_file(&include_file);
+        config.compile_protos(&protos, &includes).unwrap();
+        assert!(include_file.exists());
+        let manifest = Manifest::read(&out_dir).unwrap();
+        assert!(manifest.get("compact.rs").is_some());
+        assert!(manifest.get("mod.rs").is_none());
+        assert_eq!(manifest.files().len(), 1);
+        assert!(config.check_protos(&protos, &includes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let manifest_path = tempdir.path().join(Manifest::FILE_NAME);
+        std::fs::write(&manifest_path, "cbf29ce484222325  /etc/passwd\n").unwrap();
+        let protos = ["src/fixtures/compact/compact.proto"];
+        let includes = ["src/fixtures/compact"];
+
+        let mut config = Config::new();
+        config.out_dir(tempdir.path());
+        let error = config.compile_protos(&protos, &includes).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            error
+                .to_string()
+                .starts_with(&manifest_path.display().to_string())
+        );
+        assert!(!tempdir.path().join("compact.rs").exists());
+        let error = config.check_protos(&protos, &includes).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_config_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::copy(
+            "src/fixtures/compact/compact.proto",
+            tempdir.path().join("compact.proto"),
+        )
+        .unwrap();
+        std::fs::write(
+            tempdir.path().join("ppsc.toml"),
+            "protos = [\"compact.proto\"]\nout_dir = \"generated\"\ncompact = [\".\"]\n",
+        )
+        .unwrap();
+
+        let mut config = Config::from_file(tempdir.path().join("ppsc.toml")).unwrap();
+        config.compile().unwrap();
+        assert_eq_fixture_file!(
+            "src/fixtures/compact/_expected_compact.rs",
+            tempdir.path().join("generated/compact.rs")
+        );
+        assert!(config.check().unwrap().is_empty());
+
+        // This crate does not configure itself in its `Cargo.toml`.
+        let error = Config::from_cargo_metadata().unwrap_err();
+        assert_eq!(
+            Error::from_io_error(&error).map(Error::message),
+            Some("missing the `[package.metadata.ppsc]` table")
+        );
+    }
+
+    #[test]
+    fn test_rerun_if_changed_paths() {
+        use crate::config::compile_with_protox;
+
+        let includes = [Path::new("src/fixtures/helloworld")];
+        let fds = compile_with_protox(&["hello.proto"], &includes).unwrap();
+        assert_eq!(
+            Config::new().rerun_if_changed_paths(&fds, &includes),
+            [
+                includes[0].to_owned(),
+                includes[0].join("types.proto"),
+                includes[0].join("hello.proto"),
+            ]
+        );
+
+        // Well-known types are built into `protox`, so there is no file to watch.
+        let includes = [Path::new("src/fixtures/imports_empty")];
+        let fds = compile_with_protox(&["imports_empty.proto"], &includes).unwrap();
+        assert_eq!(
+            Config::new().rerun_if_changed_paths(&fds, &includes),
+            [
+                includes[0].to_owned(),
+                includes[0].join("imports_empty.proto")
+            ]
+        );
+
+        // The configuration file is an input of the build script too.
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_file = tempdir.path().join("ppsc.toml");
+        std::fs::write(&config_file, "protos = [\"imports_empty.proto\"]\n").unwrap();
+        std::fs::copy(
+            "src/fixtures/imports_empty/imports_empty.proto",
+            tempdir.path().join("imports_empty.proto"),
+        )
+        .unwrap();
+        let includes = [tempdir.path()];
+        let fds = compile_with_protox(&["imports_empty.proto"], &includes).unwrap();
+        assert_eq!(
+            Config::from_file(&config_file)
+                .unwrap()
+                .rerun_if_changed_paths(&fds, &includes),
+            [
+                config_file.clone(),
+                tempdir.path().to_owned(),
+                tempdir.path().join("imports_empty.proto")
+            ]
+        );
+
+        // An include directory containing the output directory changes on every build.
+        assert_eq!(
+            Config::new()
+                .out_dir(tempdir.path().join("generated"))
+                .rerun_if_changed_paths(&fds, &includes),
+            [tempdir.path().join("imports_empty.proto")]
+        );
+    }
+
+    #[test]
+    fn test_compile_error() {
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tempdir.path().join("broken.proto"),
+            "syntax = \"proto3\";\n\nmessage Broken {\n  uint32 id = 1\n}\n",
+        )
+        .unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .compile_protos(&["broken.proto"], &[tempdir.path()])
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        let Some(Error::Compile {
+            location: Some(location),
+            ..
+        }) = Error::from_io_error(&error)
+        else {
+            panic!("expected a compile error, got {:?}", error);
+        };
+        assert_eq!(location.file, "broken.proto");
+        assert_eq!(location.snippet.as_deref(), Some("}"));
+        assert!(error.to_string().contains(" --> broken.proto:5:1\n"));
+    }
+
+    #[test]
+    fn test_no_std_mode_std() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .no_std_mode(NoStdMode::Std)
+            .derives(["."], STANDARD_DERIVES)
+            .boxed(".no_std_mode.Account.parent")
+            .boxed(".no_std_mode.Account.delegate")
+            .max_len(".no_std_mode.Account.history", 16)
+            .compile_protos(
+                &["src/fixtures/no_std_mode/no_std_mode.proto"],
+                &["src/fixtures/no_std_mode"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/no_std_mode/_expected_std.rs",
+            tempdir.path().join("no_std_mode.rs")
+        );
+    }
+
+    #[test]
+    fn test_no_std_mode_feature() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .no_std_mode(NoStdMode::Feature(String::from("std")))
+            .derives(["."], STANDARD_DERIVES)
+            .boxed(".no_std_mode.Account.parent")
+            .boxed(".no_std_mode.Account.delegate")
+            .max_len(".no_std_mode.Account.history", 16)
+            .compile_protos(
+                &["src/fixtures/no_std_mode/no_std_mode.proto"],
+                &["src/fixtures/no_std_mode"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/no_std_mode/_expected_feature.rs",
+            tempdir.path().join("no_std_mode.rs")
+        );
+    }
+
+    #[test]
+    fn test_extern_crate_alloc() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .include_file("mod.rs")
+            .extern_crate_alloc(false)
+            .compile_protos(
+                &["src/fixtures/no_std_mode/no_std_mode.proto"],
+                &["src/fixtures/no_std_mode"],
+            )
+            .unwrap();
+
+        for file in ["mod.rs", "no_std_mode.rs"] {
+            let contents = std::fs::read_to_string(tempdir.path().join(file)).unwrap();
+            assert!(!contents.contains("extern crate alloc;"), "{}", contents);
+        }
+    }
+
+    #[test]
+    fn test_codec_crate() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .codec_crate("::codec")
+            .scale_info_crate("::scale_info")
+            .type_info(true)
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .max_encoded_len([".codec_crate.Kind"])
+            .compile_protos(
+                &["src/fixtures/codec_crate/codec_crate.proto"],
+                &["src/fixtures/codec_crate"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/codec_crate/_expected_codec_crate.rs",
+            tempdir.path().join("codec_crate.rs")
+        );
+    }
+
+    #[test]
+    fn test_fixed_bytes() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .derives(["."], STANDARD_DERIVES)
+            .max_encoded_len([".fixed_bytes.Header"])
+            .fixed_bytes("hash", 32)
+            .fixed_bytes(".fixed_bytes.Block.signatures", 64)
+            .fixed_bytes(".fixed_bytes.Block.state_root", 32)
+            .fixed_bytes(".fixed_bytes.Block.receipts", 32)
+            .fixed_bytes(".fixed_bytes.Block.public_key", 32)
+            .fixed_bytes(".fixed_bytes.Block.name", 8)
+            .fixed_bytes(".fixed_bytes.Header.validators", 32)
+            .max_len(".fixed_bytes.Header.validators", 100)
+            .compile_protos(
+                &["src/fixtures/fixed_bytes/fixed_bytes.proto"],
+                &["src/fixtures/fixed_bytes"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/fixed_bytes/_expected_fixed_bytes.rs",
+            tempdir.path().join("fixed_bytes.rs")
+        );
+    }
+
+    #[test]
+    fn test_default_values() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .derives(["."], STANDARD_DERIVES)
+            .encoding_profile(EncodingProfile::ProtoTypes)
+            .compact([".default_values.Settings.limit"])
+            .typed_enums([
+                ".default_values.Settings.priority",
+                ".default_values.Settings.level",
+            ])
+            .fixed_bytes(".default_values.Settings.tag", 4)
+            .compile_protos(
+                &["src/fixtures/default_values/default_values.proto"],
+                &["src/fixtures/default_values"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/default_values/_expected_default_values.rs",
+            tempdir.path().join("default_values.rs")
+        );
+    }
+
+    #[test]
+    fn test_default_values_max_len() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .max_len(".default_values.Settings.name", 8)
+            .fixed_bytes(".default_values.Settings.tag", 4)
+            .compile_protos(
+                &["src/fixtures/default_values/default_values.proto"],
+                &["src/fixtures/default_values"],
+            )
+            .unwrap_err();
+
+        let Some(Error::UnsupportedType { location, message }) = Error::from_io_error(&error)
+        else {
+            panic!("expected an unsupported type error, got {:?}", error);
+        };
+        assert_eq!(location.path, ".default_values.Settings.name");
+        assert_eq!(
+            message,
+            "field `.default_values.Settings.name`: the default value has 18 bytes, but the field holds at most 8"
+        );
+    }
+
+    #[test]
+    fn test_default_derived_by_attribute() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .type_attribute(".default_values.Settings", "#[derive(Default)]")
+            .enum_attribute(".default_values.Priority", "#[derive(Default)]")
+            .fixed_bytes(".default_values.Settings.tag", 4)
+            .compile_protos(
+                &["src/fixtures/default_values/default_values.proto"],
+                &["src/fixtures/default_values"],
+            )
+            .unwrap();
+
+        let generated = std::fs::read_to_string(tempdir.path().join("default_values.rs")).unwrap();
+        assert!(!generated.contains("impl Default for"), "{}", generated);
+        assert!(!generated.contains(", Default)]"), "{}", generated);
+        assert!(!generated.contains("#[default]"), "{}", generated);
+        // The accessors of optional fields still return the declared defaults.
+        assert!(generated.contains("pub fn retries(&self) -> i32 {"));
+    }
+
+    mod default_values {
+        include!("fixtures/default_values/_expected_default_values.rs");
+    }
+
+    #[test]
+    fn test_default_values_accessors() {
+        use default_values::{Priority, Settings};
+
+        let settings = Settings::default();
+        assert_eq!(settings.retries, None);
+        assert_eq!(settings.retries(), 3);
+        assert_eq!(settings.offset(), -42);
+        assert_eq!(settings.limit(), 1000);
+        assert_eq!(settings.ratio(), 0.5);
+        assert_eq!(settings.threshold(), f64::INFINITY);
+        assert!(settings.enabled());
+        assert_eq!(settings.name(), "unnamed \"settings\"");
+        assert_eq!(settings.magic(), b"\x00\x01PPSC\n");
+        assert_eq!(settings.priority(), &Priority::Normal);
+        assert_eq!(settings.fallback(), 1);
+        assert_eq!(settings.timeout(), 0);
+        assert_eq!(settings.version.0, 2);
+        assert_eq!(settings.level, Priority::High);
+        assert_eq!(settings.tag, *b"abcd");
+        assert!(settings.ports.is_empty());
+        assert_eq!(Priority::default(), Priority::High);
+        assert_eq!(
+            Priority::from_str_name(settings.priority().as_str_name()),
+            Some(Priority::Normal)
+        );
+
+        let settings = Settings {
+            retries: Some(default_values::ZigZagI32(5)),
+            name: Some(String::from("custom")),
+            priority: Some(Priority::Low),
+            ..Settings::default()
+        };
+        assert_eq!(settings.retries(), 5);
+        assert_eq!(settings.name(), "custom");
+        assert_eq!(settings.priority(), &Priority::Low);
+    }
+
+    #[test]
+    fn test_max_encoded_len_unbounded_fields() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let error = Config::new()
+            .out_dir(tempdir.path())
+            .max_encoded_len([".max_encoded_len.Audit"])
+            .max_len(".max_encoded_len.Audit.tags", 4)
+            .max_len(".max_encoded_len.Audit.counters", 4)
+            .compile_protos(
+                &["src/fixtures/max_encoded_len/max_encoded_len.proto"],
+                &["src/fixtures/max_encoded_len"],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        let message = error.to_string();
+        for expected in [
+            "`.max_encoded_len.Audit.note` (string without a maximum length)",
+            "`.max_encoded_len.Audit.tags` (string elements have no maximum length)",
+            "`.max_encoded_len.Audit.counters` (string keys have no maximum length)",
+            "`.max_encoded_len.Audit.detail` (`.max_encoded_len.Unbounded` does not derive `MaxEncodedLen`)",
+        ] {
+            assert!(message.contains(expected), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_enum_index() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .enum_index([".enum_index.Priority"], EnumIndex::DeclarationOrder)
+            .codec_index(".enum_index.Priority.PRIORITY_UNKNOWN", 255)
+            .compile_protos(
+                &["src/fixtures/enum_index/enum_index.proto"],
+                &["src/fixtures/enum_index"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/enum_index/_expected_enum_index.rs",
+            tempdir.path().join("enum_index.rs")
+        );
+    }
+
+    #[test]
+    fn test_enum_index_out_of_range() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let err = Config::new()
+            .out_dir(tempdir.path())
+            .compile_protos(
+                &["src/fixtures/enum_index/enum_index.proto"],
+                &["src/fixtures/enum_index"],
+            )
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("enum `.enum_index.Priority`"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_oneof_index() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .oneof_index(["Payment.method"], OneofIndex::FieldNumber)
+            .compile_protos(
+                &["src/fixtures/oneof_index/oneof_index.proto"],
+                &["src/fixtures/oneof_index"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/oneof_index/_expected_oneof_index.rs",
+            tempdir.path().join("oneof_index.rs")
+        );
+    }
+
+    #[test]
+    fn test_oneof_index_out_of_range() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let err = Config::new()
+            .out_dir(tempdir.path())
+            .oneof_index(["."], OneofIndex::FieldNumber)
+            .compile_protos(
+                &["src/fixtures/oneof_index/oneof_index.proto"],
+                &["src/fixtures/oneof_index"],
+            )
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("oneof `.oneof_index.Wide.choice`"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_field_order() {
+        let cases = [
+            (
+                FieldOrder::Legacy,
+                "src/fixtures/field_order/_expected_field_order_legacy.rs",
+            ),
+            (
+                FieldOrder::Declaration,
+                "src/fixtures/field_order/_expected_field_order_declaration.rs",
+            ),
+            (
+                FieldOrder::FieldNumber,
+                "src/fixtures/field_order/_expected_field_order_field_number.rs",
+            ),
+        ];
+
+        for (order, expected) in cases {
+            let tempdir = tempfile::tempdir().unwrap();
+
+            Config::new()
+                .out_dir(tempdir.path())
+                .field_order(order)
+                .compile_protos(
+                    &["src/fixtures/field_order/field_order.proto"],
+                    &["src/fixtures/field_order"],
+                )
+                .unwrap();
+
+            assert_eq_fixture_file!(expected, tempdir.path().join("field_order.rs"));
+        }
+    }
+
+    #[test]
+    fn test_network_protocol_declaration_order() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        Config::new()
+            .out_dir(tempdir.path())
+            .field_order(FieldOrder::Declaration)
+            .compile_protos(
+                &["src/fixtures/network_protocol/network_protocol.proto"],
+                &["src/fixtures/network_protocol"],
+            )
+            .unwrap();
+
+        assert_eq_fixture_file!(
+            "src/fixtures/network_protocol/_expected_network_protocol_declaration.rs",
+            tempdir.path().join("network.protocol.rs")
+        );
+    }
+
     #[test]
     fn test_tutorial() {
         let tempdir = tempfile::tempdir().unwrap();
@@ -0,0 +1,217 @@
+//! The manifest of the files generated in an output directory.
+
+use std::fmt;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Component, Path};
+
+/// The list of the files generated in an output directory, with hashes of their contents.
+///
+/// Code generation writes the manifest to [`Manifest::FILE_NAME`] in the output directory,
+/// along with the generated files. On the next run, the files it lists that are no longer
+/// generated, such as those of a removed package, are deleted, unless they were modified since
+/// they were generated, in which case they are kept and stay listed. Tools can read it to find
+/// the generated files, and to tell whether they were modified since.
+///
+/// The manifest is a text file with a line per generated file, holding the hash of the file
+/// and its name relative to the output directory:
+///
+/// ```text
+/// # This file is @generated by ppsc-build.
+/// 3b1f7a9c0d2e4f68  items.rs
+/// 9e0c4d2a7b6f1835  mod.rs
+/// ```
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn main() -> std::io::Result<()> {
+/// use ppsc_build::Manifest;
+///
+/// let manifest = Manifest::read("src/generated")?;
+/// for file in manifest.files() {
+///     let content = std::fs::read(std::path::Path::new("src/generated").join(&file.name))?;
+///     if Manifest::content_hash(&content) != file.hash {
+///         eprintln!("{} was modified since it was generated", file.name);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    files: Vec<ManifestFile>,
+}
+
+/// A file listed in a [`Manifest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestFile {
+    /// The name of the file, relative to the output directory.
+    pub name: String,
+    /// The [`Manifest::content_hash`] of the generated contents of the file.
+    pub hash: u64,
+}
+
+impl Manifest {
+    /// The name of the manifest file in the output directory.
+    pub const FILE_NAME: &'static str = ".ppsc-manifest";
+
+    /// Reads the manifest of an output directory.
+    ///
+    /// Fails with an error of kind [`NotFound`](ErrorKind::NotFound) when the directory has no
+    /// manifest, and [`InvalidData`](ErrorKind::InvalidData) when it is malformed.
+    pub fn read(out_dir: impl AsRef<Path>) -> Result<Manifest> {
+        let contents = fs::read_to_string(out_dir.as_ref().join(Manifest::FILE_NAME))?;
+        contents.parse()
+    }
+
+    /// Returns the files generated in the output directory, sorted by name.
+    pub fn files(&self) -> &[ManifestFile] {
+        &self.files
+    }
+
+    /// Returns the generated file with the given name, relative to the output directory.
+    pub fn get(&self, name: &str) -> Option<&ManifestFile> {
+        self.files.iter().find(|file| file.name == name)
+    }
+
+    /// Hashes the contents of a file, as recorded in the manifest.
+    ///
+    /// This is the 64-bit FNV-1a hash, which is stable across platforms and releases.
+    pub fn content_hash(content: &[u8]) -> u64 {
+        content.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+
+    /// Creates the manifest of generated files, as file names and contents.
+    ///
+    /// Files whose names are not relative to the output directory, such as an include file
+    /// outside of it, are left out.
+    pub(crate) fn from_files(files: &[(String, String)]) -> Manifest {
+        let mut files: Vec<ManifestFile> = files
+            .iter()
+            .filter(|(name, _)| is_relative_name(name))
+            .map(|(name, content)| ManifestFile {
+                name: name.clone(),
+                hash: Manifest::content_hash(content.as_bytes()),
+            })
+            .collect();
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Manifest { files }
+    }
+
+    /// Adds files to the manifest, keeping it sorted by name.
+    pub(crate) fn add_files(&mut self, files: impl IntoIterator<Item = ManifestFile>) {
+        self.files.extend(files);
+        self.files.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+impl std::str::FromStr for Manifest {
+    type Err = Error;
+
+    fn from_str(contents: &str) -> Result<Manifest> {
+        let mut files = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let file = line.split_once("  ").and_then(|(hash, name)| {
+                if !is_relative_name(name) {
+                    return None;
+                }
+                Some(ManifestFile {
+                    name: name.to_owned(),
+                    hash: u64::from_str_radix(hash, 16).ok()?,
+                })
+            });
+            files.push(file.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid manifest line {}: `{}`", index + 1, line),
+                )
+            })?);
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Manifest { files })
+    }
+}
+
+/// Returns whether `name` is a file name relative to the output directory that stays within it,
+/// since the files the manifest lists may be deleted.
+fn is_relative_name(name: &str) -> bool {
+    let mut components = Path::new(name).components().peekable();
+    components.peek().is_some()
+        && components.all(|component| matches!(component, Component::Normal(_)))
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# This file is @generated by ppsc-build.")?;
+        for file in &self.files {
+            writeln!(f, "{:016x}  {}", file.hash, file.name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(Manifest::content_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(Manifest::content_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let manifest = Manifest::from_files(&[
+            (
+                String::from("mod.rs"),
+                String::from("include!(\"items.rs\");\n"),
+            ),
+            (String::from("items.rs"), String::new()),
+        ]);
+        let names: Vec<&str> = manifest
+            .files()
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect();
+        assert_eq!(names, ["items.rs", "mod.rs"]);
+        assert_eq!(
+            manifest.get("items.rs").map(|file| file.hash),
+            Some(0xcbf2_9ce4_8422_2325)
+        );
+
+        let contents = manifest.to_string();
+        assert!(contents.contains("\ncbf29ce484222325  items.rs\n"));
+        assert_eq!(contents.parse::<Manifest>().unwrap(), manifest);
+
+        let error = "# header\nnot a hash  items.rs\n"
+            .parse::<Manifest>()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "invalid manifest line 2: `not a hash  items.rs`"
+        );
+
+        for line in [
+            "cbf29ce484222325  /etc/passwd",
+            "cbf29ce484222325  ../items.rs",
+            "cbf29ce484222325  nested/../../items.rs",
+            "cbf29ce484222325  ",
+        ] {
+            let error = line.parse::<Manifest>().unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{}", line);
+        }
+        assert!(
+            "cbf29ce484222325  nested/items.rs"
+                .parse::<Manifest>()
+                .is_ok()
+        );
+    }
+}
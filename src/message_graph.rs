@@ -5,10 +5,13 @@ use petgraph::algo::has_path_connecting;
 use petgraph::graph::NodeIndex;
 
 use prost_types::{
-    DescriptorProto, FileDescriptorProto,
+    DescriptorProto, EnumDescriptorProto, FileDescriptorProto,
     field_descriptor_proto::{Label, Type},
 };
 
+use crate::error::{Error, Location, Span};
+use crate::features::{DescriptorFeatures, Edition, Features};
+
 /// `MessageGraph` builds a graph of messages whose edges correspond to nesting.
 /// The goal is to recognize when message types are recursively nested, so
 /// that fields can be boxed when necessary.
@@ -16,14 +19,29 @@ pub struct MessageGraph {
     index: HashMap<String, NodeIndex>,
     graph: Graph<String, ()>,
     messages: HashMap<String, DescriptorProto>,
+    enums: HashMap<String, EnumDescriptorProto>,
+    /// The resolved features of every field, by fully-qualified field name.
+    field_features: HashMap<String, Features>,
+    /// The resolved features of every enum, by fully-qualified name.
+    enum_features: HashMap<String, Features>,
 }
 
 impl MessageGraph {
-    pub(crate) fn new<'a>(files: impl Iterator<Item = &'a FileDescriptorProto>) -> MessageGraph {
+    /// Builds the graph of the messages in `files`, resolving the features of their fields and
+    /// enums from the file editions and the overrides in `features`.
+    ///
+    /// Fails on files with an unknown syntax or edition.
+    pub(crate) fn new<'a>(
+        files: impl Iterator<Item = &'a FileDescriptorProto>,
+        features: &DescriptorFeatures,
+    ) -> std::io::Result<MessageGraph> {
         let mut msg_graph = MessageGraph {
             index: HashMap::new(),
             graph: Graph::new(),
             messages: HashMap::new(),
+            enums: HashMap::new(),
+            field_features: HashMap::new(),
+            enum_features: HashMap::new(),
         };
 
         for file in files {
@@ -32,12 +50,35 @@ impl MessageGraph {
                 if file.package.is_some() { "." } else { "" },
                 file.package.as_ref().map(String::as_str).unwrap_or("")
             );
+            let edition = if file.syntax() == "editions" && !features.is_decoded() {
+                Err(format!(
+                    "file `{}`: the edition and features of Editions files are not retained by `FileDescriptorSet`; compile the encoded descriptors with `Config::compile_encoded_fds`",
+                    file.name()
+                ))
+            } else {
+                Edition::of(file, features.edition(file.name()))
+            };
+            let edition = edition.map_err(|message| {
+                // The span of the `edition` declaration, or of the `syntax` one.
+                let span_path = if file.syntax() == "editions" { 14 } else { 12 };
+                Error::InvalidSyntax {
+                    location: Location {
+                        file: file.name().to_string(),
+                        path: package.clone(),
+                        span: Span::find(file.source_code_info.as_ref(), &[span_path]),
+                        snippet: None,
+                    },
+                    message,
+                }
+            })?;
+            let file_features = Features::defaults(edition).merge(features.file(file.name()));
             for msg in &file.message_type {
-                msg_graph.add_message(&package, msg);
+                msg_graph.add_message(&package, msg, file_features, features);
             }
+            msg_graph.add_enums(&package, &file.enum_type, file_features, features);
         }
 
-        msg_graph
+        Ok(msg_graph)
     }
 
     fn get_or_insert_index(&mut self, msg_name: String) -> NodeIndex {
@@ -57,23 +98,78 @@ impl MessageGraph {
     /// Because prost does not box message fields, recursively nested messages would not compile in Rust.
     /// To allow recursive messages, the message graph is used to detect recursion and automatically box the recursive field.
     /// Since repeated messages are already put in a Vec, boxing them isn’t necessary even if the reference is recursive.
-    fn add_message(&mut self, package: &str, msg: &DescriptorProto) {
-        let msg_name = format!("{}.{}", package, msg.name.as_ref().unwrap());
+    fn add_message(
+        &mut self,
+        package: &str,
+        msg: &DescriptorProto,
+        parent_features: Features,
+        features: &DescriptorFeatures,
+    ) {
+        let msg_name = format!("{}.{}", package, msg.name());
+        let msg_features = parent_features.merge(features.element(&msg_name));
         let msg_index = self.get_or_insert_index(msg_name.clone());
 
         for field in &msg.field {
-            if field.r#type() == Type::Message && field.label() != Label::Repeated {
-                let field_index = self.get_or_insert_index(field.type_name.clone().unwrap());
+            // Type names that are not fully qualified are reported by the code generator.
+            if field.r#type() == Type::Message
+                && field.label() != Label::Repeated
+                && field.type_name().starts_with('.')
+            {
+                let field_index = self.get_or_insert_index(field.type_name().to_string());
                 self.graph.add_edge(msg_index, field_index, ());
             }
         }
+        for field in &msg.field {
+            let field_name = format!("{}.{}", msg_name, field.name());
+            let field_features = msg_features.merge(features.element(&field_name));
+            self.field_features.insert(field_name, field_features);
+        }
         self.messages.insert(msg_name.clone(), msg.clone());
 
         for msg in &msg.nested_type {
-            self.add_message(&msg_name, msg);
+            self.add_message(&msg_name, msg, msg_features, features);
+        }
+        self.add_enums(&msg_name, &msg.enum_type, msg_features, features);
+    }
+
+    /// Returns the descriptor of the named message, if it is part of the generated files.
+    pub fn get_message(&self, fq_message_name: &str) -> Option<&DescriptorProto> {
+        self.messages.get(fq_message_name)
+    }
+
+    fn add_enums(
+        &mut self,
+        scope: &str,
+        enums: &[EnumDescriptorProto],
+        parent_features: Features,
+        features: &DescriptorFeatures,
+    ) {
+        for enum_type in enums {
+            let enum_name = format!("{}.{}", scope, enum_type.name());
+            let enum_features = parent_features.merge(features.element(&enum_name));
+            self.enum_features.insert(enum_name.clone(), enum_features);
+            self.enums.insert(enum_name, enum_type.clone());
         }
     }
 
+    /// Returns the descriptor of the named enum, if it is part of the generated files.
+    pub fn get_enum(&self, fq_enum_name: &str) -> Option<&EnumDescriptorProto> {
+        self.enums.get(fq_enum_name)
+    }
+
+    /// Returns the resolved features of the named message field, if the message is part of the
+    /// generated files.
+    pub fn field_features(&self, fq_message_name: &str, field_name: &str) -> Option<Features> {
+        self.field_features
+            .get(&format!("{}.{}", fq_message_name, field_name))
+            .copied()
+    }
+
+    /// Returns the resolved features of the named enum, if it is part of the generated files.
+    pub fn enum_features(&self, fq_enum_name: &str) -> Option<Features> {
+        self.enum_features.get(fq_enum_name).copied()
+    }
+
     /// Returns true if message type `inner` is nested in message type `outer`.
     pub fn is_nested(&self, outer: &str, inner: &str) -> bool {
         let outer = match self.index.get(outer) {
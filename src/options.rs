@@ -0,0 +1,580 @@
+//! Configuration options set by name, for the `protoc` plugin, the command line and
+//! configuration files.
+
+use std::io::{self, Result};
+use std::path::Path;
+use std::str::FromStr;
+use std::{env, fs};
+
+use toml::{Table, Value};
+
+use crate::{
+    BytesType, Config, EncodingProfile, EnumIndex, Error, FieldOrder, FloatStrategy, MapType,
+    NoStdMode, OneofIndex, SignedVarint,
+};
+
+impl Config {
+    /// Sets an option by the name of the method that sets it, from a textual value.
+    ///
+    /// This is the form options take in `protoc` plugin parameters, where each `key=value`
+    /// pair is passed to this method. Options that take paths add to the paths set so far,
+    /// rather than replacing them like the methods do.
+    ///
+    /// | Key | Value |
+    /// |-----|-------|
+    /// | `btree_map`, `hash_map`, `bytes`, `boxed`, `typed_enums`, `compact`, `max_encoded_len`, `disable_comments`, `skip_debug` | a path |
+    /// | `hash_map_path`, `bytes_path`, `codec_crate`, `scale_info_crate`, `default_package_filename` | a Rust path or name |
+    /// | `out_dir`, `include_file` | a file path |
+    /// | `protos`, `includes` | a file path, for [`compile`](Self::compile) |
+    /// | `type_info`, `retain_enum_prefix`, `extern_crate_alloc`, `emit_rerun_if_changed` | `true` or `false`, or nothing for `true` |
+    /// | `field_attribute`, `type_attribute`, `message_attribute`, `enum_attribute` | `PATH=ATTRIBUTE` |
+    /// | `extern_path` | `PROTO_PATH=RUST_PATH` |
+    /// | `type_name_domain` | `PATH=DOMAIN` |
+    /// | `max_len`, `fixed_bytes`, `codec_index` | `PATH=NUMBER` |
+    /// | `derives` | `PATH=DERIVE+DERIVE+...` |
+    /// | `enum_index` | `PATH=proto_number` or `PATH=declaration_order` |
+    /// | `oneof_index` | `PATH=declaration_order` or `PATH=field_number` |
+    /// | `encoding_profile` | `fixed_width` or `proto_types` |
+    /// | `signed_varint` | `zig_zag`, `fixed_width` or `reject` |
+    /// | `float_strategy` | `bit_cast`, `reject` or `wrapper=F32_TYPE=F64_TYPE` |
+    /// | `no_std_mode` | `alloc`, `std` or `feature=NAME` |
+    /// | `field_order` | `legacy`, `declaration` or `field_number` |
+    ///
+    /// Fails with an [`Error::InvalidOption`] for unknown keys and invalid values, including
+    /// paths that are not Protobuf paths.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut config = ppsc_build::Config::new();
+    /// config
+    ///     .set_option("compact", ".my_messages")?
+    ///     .set_option("max_len", ".my_messages.Item.name=64")?
+    ///     .set_option("derives", ".=Clone+PartialEq+Eq")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_option<'a>(&mut self, key: &str, value: &'a str) -> Result<&mut Self> {
+        let invalid = |expected: &str| -> std::io::Error {
+            Error::InvalidOption {
+                key: key.to_owned(),
+                message: format!("option `{}`: expected {}, got `{}`", key, expected, value),
+            }
+            .into()
+        };
+        let proto_path = |path: &'a str| -> Result<&'a str> {
+            if is_proto_path(path) {
+                Ok(path)
+            } else {
+                Err(Error::InvalidOption {
+                    key: key.to_owned(),
+                    message: format!("option `{}`: invalid Protobuf path `{}`", key, path),
+                }
+                .into())
+            }
+        };
+        let path_value = |expected: &str| -> Result<(&'a str, &'a str)> {
+            let (path, value) = value.split_once('=').ok_or_else(|| invalid(expected))?;
+            Ok((proto_path(path)?, value))
+        };
+
+        match key {
+            "btree_map" => self
+                .map_type
+                .insert(proto_path(value)?.to_owned(), MapType::BTreeMap),
+            "hash_map" => self
+                .map_type
+                .insert(proto_path(value)?.to_owned(), MapType::HashMap),
+            "bytes" => self
+                .bytes_type
+                .insert(proto_path(value)?.to_owned(), BytesType::Bytes),
+            "boxed" => self.boxed.insert(proto_path(value)?.to_owned(), ()),
+            "typed_enums" => self.typed_enums.insert(proto_path(value)?.to_owned(), ()),
+            "compact" => self.compact.insert(proto_path(value)?.to_owned(), ()),
+            "max_encoded_len" => self
+                .max_encoded_len
+                .insert(proto_path(value)?.to_owned(), ()),
+            "disable_comments" => self
+                .disable_comments
+                .insert(proto_path(value)?.to_owned(), ()),
+            "skip_debug" => self.skip_debug.insert(proto_path(value)?.to_owned(), ()),
+            "protos" => self.protos.push(value.into()),
+            "includes" => self.includes.push(value.into()),
+            "hash_map_path" => self.hash_map_path = Some(value.to_owned()),
+            "bytes_path" => self.bytes_path = Some(value.to_owned()),
+            "codec_crate" => self.codec_crate = Some(value.to_owned()),
+            "scale_info_crate" => self.scale_info_crate = Some(value.to_owned()),
+            "default_package_filename" => self.default_package_filename = value.to_owned(),
+            "out_dir" => self.out_dir = Some(value.into()),
+            "include_file" => self.include_file = Some(value.into()),
+            "type_info" => {
+                self.type_info = parse_bool(value).ok_or_else(|| invalid("`true` or `false`"))?;
+            }
+            "extern_crate_alloc" => {
+                self.extern_crate_alloc =
+                    parse_bool(value).ok_or_else(|| invalid("`true` or `false`"))?;
+            }
+            "emit_rerun_if_changed" => {
+                self.emit_rerun_if_changed =
+                    parse_bool(value).ok_or_else(|| invalid("`true` or `false`"))?;
+            }
+            "retain_enum_prefix" => {
+                self.strip_enum_prefix =
+                    !parse_bool(value).ok_or_else(|| invalid("`true` or `false`"))?;
+            }
+            "field_attribute" | "type_attribute" | "message_attribute" | "enum_attribute" => {
+                let (path, attribute) = path_value("`PATH=ATTRIBUTE`")?;
+                match key {
+                    "field_attribute" => self.field_attribute(path, attribute),
+                    "type_attribute" => self.type_attribute(path, attribute),
+                    "message_attribute" => self.message_attribute(path, attribute),
+                    _ => self.enum_attribute(path, attribute),
+                };
+            }
+            "extern_path" => {
+                let (path, rust_path) = path_value("`PROTO_PATH=RUST_PATH`")?;
+                self.extern_path(path, rust_path);
+            }
+            "type_name_domain" => {
+                let (path, domain) = path_value("`PATH=DOMAIN`")?;
+                self.type_name_domain([path], domain);
+            }
+            "max_len" => {
+                let (path, max_len) = path_number(value).ok_or_else(|| invalid("`PATH=LENGTH`"))?;
+                self.max_len(proto_path(path)?, max_len);
+            }
+            "fixed_bytes" => {
+                let (path, len) = path_number(value).ok_or_else(|| invalid("`PATH=LENGTH`"))?;
+                self.fixed_bytes(proto_path(path)?, len);
+            }
+            "codec_index" => {
+                let (path, index) = path_number(value)
+                    .ok_or_else(|| invalid("`PATH=INDEX` with an index in 0..=255"))?;
+                self.codec_index(proto_path(path)?, index);
+            }
+            "derives" => {
+                let (path, derives) = path_value("`PATH=DERIVE+DERIVE+...`")?;
+                self.derives([path], derives.split('+').map(str::trim));
+            }
+            "enum_index" => {
+                let expected = "`PATH=proto_number` or `PATH=declaration_order`";
+                let (path, index) = path_value(expected)?;
+                let index = match index {
+                    "proto_number" => EnumIndex::ProtoNumber,
+                    "declaration_order" => EnumIndex::DeclarationOrder,
+                    _ => return Err(invalid(expected)),
+                };
+                self.enum_index([path], index);
+            }
+            "oneof_index" => {
+                let expected = "`PATH=declaration_order` or `PATH=field_number`";
+                let (path, index) = path_value(expected)?;
+                let index = match index {
+                    "declaration_order" => OneofIndex::DeclarationOrder,
+                    "field_number" => OneofIndex::FieldNumber,
+                    _ => return Err(invalid(expected)),
+                };
+                self.oneof_index([path], index);
+            }
+            "encoding_profile" => {
+                self.encoding_profile = match value {
+                    "fixed_width" => EncodingProfile::FixedWidth,
+                    "proto_types" => EncodingProfile::ProtoTypes,
+                    _ => return Err(invalid("`fixed_width` or `proto_types`")),
+                };
+            }
+            "signed_varint" => {
+                self.signed_varint = match value {
+                    "zig_zag" => SignedVarint::ZigZag,
+                    "fixed_width" => SignedVarint::FixedWidth,
+                    "reject" => SignedVarint::Reject,
+                    _ => return Err(invalid("`zig_zag`, `fixed_width` or `reject`")),
+                };
+            }
+            "float_strategy" => {
+                self.float_strategy = match value.split('=').collect::<Vec<_>>()[..] {
+                    ["bit_cast"] => FloatStrategy::BitCast,
+                    ["reject"] => FloatStrategy::Reject,
+                    ["wrapper", f32, f64] => FloatStrategy::Wrapper {
+                        f32: f32.to_owned(),
+                        f64: f64.to_owned(),
+                    },
+                    _ => {
+                        return Err(invalid(
+                            "`bit_cast`, `reject` or `wrapper=F32_TYPE=F64_TYPE`",
+                        ));
+                    }
+                };
+            }
+            "no_std_mode" => {
+                self.no_std_mode = match value.split_once('=') {
+                    None if value == "alloc" => NoStdMode::Alloc,
+                    None if value == "std" => NoStdMode::Std,
+                    Some(("feature", feature)) => NoStdMode::Feature(feature.to_owned()),
+                    _ => return Err(invalid("`alloc`, `std` or `feature=NAME`")),
+                };
+            }
+            "field_order" => {
+                self.field_order = match value {
+                    "legacy" => FieldOrder::Legacy,
+                    "declaration" => FieldOrder::Declaration,
+                    "field_number" => FieldOrder::FieldNumber,
+                    _ => return Err(invalid("`legacy`, `declaration` or `field_number`")),
+                };
+            }
+            _ => {
+                return Err(Error::InvalidOption {
+                    key: key.to_owned(),
+                    message: format!("unknown option `{}`", key),
+                }
+                .into());
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl Config {
+    /// Loads a configuration from a TOML file, such as `ppsc.toml`.
+    ///
+    /// The keys of the file are the options of [`set_option`](Self::set_option). Options that
+    /// can be set several times take an array, and options that take `PATH=VALUE` take a table
+    /// from paths to values, or to arrays of values:
+    ///
+    /// ```toml
+    /// protos = ["items.proto"]
+    /// includes = ["."]
+    /// out_dir = "src/generated"
+    /// include_file = "mod.rs"
+    /// type_info = true
+    /// boxed = [".items.Tree.left", ".items.Tree.right"]
+    /// bytes = ["."]
+    /// btree_map = ["."]
+    /// extern_path = { ".common" = "::common" }
+    /// type_attribute = { ".items.Item" = ["#[derive(Hash)]", "#[repr(C)]"] }
+    /// max_len = { ".items.Item.name" = 64 }
+    /// derives = { "." = ["Clone", "PartialEq"] }
+    /// ```
+    ///
+    /// The `protos`, `includes` and `out_dir` paths are relative to the directory of the file,
+    /// which is also the include directory when there are none. Compile the `.proto` files with
+    /// [`compile`](Self::compile).
+    ///
+    /// Fails with an [`Error::ConfigFile`] for invalid TOML, unknown keys, invalid values and
+    /// `.proto` files that do not exist.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Config> {
+        let path = path.as_ref();
+        let table = parse_toml(path, &fs::read_to_string(path)?)?;
+        Config::from_table(path, table)
+    }
+
+    /// Loads a configuration from the `[package.metadata.ppsc]` table of the `Cargo.toml` of
+    /// the package being built, in the format of [`from_file`](Self::from_file).
+    ///
+    /// # Example
+    ///
+    /// ```toml
+    /// [package.metadata.ppsc]
+    /// protos = ["src/items.proto"]
+    /// includes = ["src"]
+    /// compact = ["."]
+    /// ```
+    ///
+    /// ```rust,no_run
+    /// // build.rs
+    /// fn main() -> std::io::Result<()> {
+    ///   ppsc_build::Config::from_cargo_metadata()?.compile()
+    /// }
+    /// ```
+    pub fn from_cargo_metadata() -> Result<Config> {
+        let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
+            io::Error::other("CARGO_MANIFEST_DIR environment variable is not set")
+        })?;
+        let path = Path::new(&manifest_dir).join("Cargo.toml");
+        let mut table = parse_toml(&path, &fs::read_to_string(&path)?)?;
+
+        let metadata = table
+            .remove("package")
+            .and_then(|package| package.get("metadata")?.get("ppsc").cloned());
+        match metadata {
+            Some(Value::Table(metadata)) => Config::from_table(&path, metadata),
+            Some(_) => Err(config_file_error(
+                &path,
+                String::from("`package.metadata.ppsc` is not a table"),
+            )),
+            None => Err(config_file_error(
+                &path,
+                String::from("missing the `[package.metadata.ppsc]` table"),
+            )),
+        }
+    }
+
+    fn from_table(file: &Path, table: Table) -> Result<Config> {
+        let base = match file.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        let mut config = Config::new();
+        config.config_file = Some(file.to_owned());
+        for (key, value) in table {
+            let values =
+                option_values(&key, value).map_err(|message| config_file_error(file, message))?;
+            for value in values {
+                let value = match key.as_str() {
+                    "protos" | "includes" | "out_dir" => {
+                        base.join(value).to_string_lossy().into_owned()
+                    }
+                    _ => value,
+                };
+                config.set_option(&key, &value).map_err(|error| {
+                    match Error::from_io_error(&error) {
+                        Some(error) => config_file_error(file, error.message().to_owned()),
+                        None => error,
+                    }
+                })?;
+            }
+        }
+
+        if let Some(proto) = config.protos.iter().find(|proto| !proto.is_file()) {
+            return Err(config_file_error(
+                file,
+                format!("`.proto` file `{}` does not exist", proto.display()),
+            ));
+        }
+        if config.includes.is_empty() {
+            config.includes.push(base.to_owned());
+        }
+        Ok(config)
+    }
+}
+
+fn config_file_error(file: &Path, message: String) -> io::Error {
+    Error::ConfigFile {
+        file: file.display().to_string(),
+        message,
+    }
+    .into()
+}
+
+fn parse_toml(file: &Path, contents: &str) -> Result<Table> {
+    contents.parse::<Table>().map_err(|error| {
+        let position = error.span().map(|span| {
+            let before = &contents[..span.start];
+            let line = before.matches('\n').count() + 1;
+            let column = before.len() - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+            format!(" at line {}, column {}", line, column)
+        });
+        config_file_error(
+            file,
+            format!(
+                "invalid TOML: {}{}",
+                error.message().trim_end(),
+                position.unwrap_or_default()
+            ),
+        )
+    })
+}
+
+/// Converts the value of a configuration file key to the values of
+/// [`Config::set_option`] calls.
+fn option_values(key: &str, value: Value) -> std::result::Result<Vec<String>, String> {
+    let scalar = |value: Value| match value {
+        Value::String(value) => Ok(value),
+        Value::Integer(value) => Ok(value.to_string()),
+        Value::Boolean(value) if BOOLEAN_OPTIONS.contains(&key) => Ok(value.to_string()),
+        value => Err(format!(
+            "option `{}`: unexpected {} value",
+            key,
+            value.type_str()
+        )),
+    };
+
+    match value {
+        Value::Array(values) => values.into_iter().map(scalar).collect(),
+        Value::Table(table) => {
+            let mut values = Vec::new();
+            for (path, value) in table {
+                match value {
+                    Value::Array(items) if key == "derives" => {
+                        let derives = items
+                            .into_iter()
+                            .map(scalar)
+                            .collect::<std::result::Result<Vec<_>, _>>()?;
+                        values.push(format!("{}={}", path, derives.join("+")));
+                    }
+                    Value::Array(items) => {
+                        for item in items {
+                            values.push(format!("{}={}", path, scalar(item)?));
+                        }
+                    }
+                    value => values.push(format!("{}={}", path, scalar(value)?)),
+                }
+            }
+            Ok(values)
+        }
+        value => Ok(vec![scalar(value)?]),
+    }
+}
+
+/// Splits a `PATH=NUMBER` value.
+fn path_number<T: FromStr>(value: &str) -> Option<(&str, T)> {
+    let (path, number) = value.split_once('=')?;
+    Some((path, number.parse().ok()?))
+}
+
+/// Returns whether `path` is `.` or a Protobuf path, fully-qualified or relative.
+fn is_proto_path(path: &str) -> bool {
+    path == "."
+        || path
+            .strip_prefix('.')
+            .unwrap_or(path)
+            .split('.')
+            .all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            })
+}
+
+/// The options of [`Config::set_option`](crate::Config::set_option) that take a boolean.
+///
+/// An empty value sets them to `true`, and they are the only options a configuration file can
+/// set to a boolean.
+pub const BOOLEAN_OPTIONS: &[&str] = &[
+    "type_info",
+    "retain_enum_prefix",
+    "extern_crate_alloc",
+    "emit_rerun_if_changed",
+];
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "" | "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_option() {
+        let mut config = Config::new();
+        config
+            .set_option("compact", ".a")
+            .unwrap()
+            .set_option("compact", ".b")
+            .unwrap()
+            .set_option("type_info", "")
+            .unwrap()
+            .set_option("max_len", ".a.Item.name=64")
+            .unwrap()
+            .set_option("derives", ".a=Clone + PartialEq")
+            .unwrap()
+            .set_option("no_std_mode", "feature=std")
+            .unwrap()
+            .set_option("extern_crate_alloc", "false")
+            .unwrap()
+            .set_option("float_strategy", "wrapper=crate::F32=crate::F64")
+            .unwrap();
+
+        assert!(config.compact.get_first(".a.Item").is_some());
+        assert!(config.compact.get_first(".b.Item").is_some());
+        assert!(config.type_info);
+        assert_eq!(
+            config.max_len.get_first_field(".a.Item", "name").copied(),
+            Some(64)
+        );
+        assert_eq!(
+            config.derives.get_first(".a.Item").cloned(),
+            Some(vec![String::from("Clone"), String::from("PartialEq")])
+        );
+        assert_eq!(config.no_std_mode, NoStdMode::Feature(String::from("std")));
+        assert!(!config.extern_crate_alloc);
+        assert_eq!(
+            config.float_strategy,
+            FloatStrategy::Wrapper {
+                f32: String::from("crate::F32"),
+                f64: String::from("crate::F64"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_option_errors() {
+        let mut config = Config::new();
+
+        let error = config.set_option("btree_maps", ".").unwrap_err();
+        assert_eq!(
+            Error::from_io_error(&error).map(Error::message),
+            Some("unknown option `btree_maps`")
+        );
+
+        let error = config.set_option("max_len", ".a.Item.name").unwrap_err();
+        assert_eq!(
+            Error::from_io_error(&error).map(Error::message),
+            Some("option `max_len`: expected `PATH=LENGTH`, got `.a.Item.name`")
+        );
+
+        let error = config
+            .set_option("field_attribute", ".a..b=#[x]")
+            .unwrap_err();
+        assert_eq!(
+            Error::from_io_error(&error).map(Error::message),
+            Some("option `field_attribute`: invalid Protobuf path `.a..b`")
+        );
+
+        assert!(config.set_option("boxed", "a b").is_err());
+        assert!(config.set_option("codec_index", ".a.Kind.ONE=256").is_err());
+        assert!(config.set_option("type_info", "yes").is_err());
+        assert!(config.set_option("no_std_mode", "core").is_err());
+    }
+
+    #[test]
+    fn test_from_file_errors() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let file = tempdir.path().join("ppsc.toml");
+        let message = |contents: &str| {
+            fs::write(&file, contents).unwrap();
+            let error = Config::from_file(&file).unwrap_err();
+            match Error::from_io_error(&error) {
+                Some(Error::ConfigFile { message, .. }) => message.clone(),
+                _ => panic!("expected a configuration file error, got {:?}", error),
+            }
+        };
+
+        assert_eq!(message("compacts = [\".\"]"), "unknown option `compacts`");
+        assert_eq!(
+            message("compact = true"),
+            "option `compact`: unexpected boolean value"
+        );
+        assert_eq!(
+            message("max_len = { \".a.B.c\" = 1.5 }"),
+            "option `max_len`: unexpected float value"
+        );
+        assert_eq!(
+            message("boxed = [\".a.\"]"),
+            "option `boxed`: invalid Protobuf path `.a.`"
+        );
+        assert_eq!(
+            message("protos = [\"missing.proto\"]"),
+            format!(
+                "`.proto` file `{}` does not exist",
+                tempdir.path().join("missing.proto").display()
+            )
+        );
+        assert!(message("compact = [\".\"\n").starts_with("invalid TOML: "));
+        assert!(message("compact = [\".\"\n").ends_with(" at line 2, column 1"));
+
+        fs::write(&file, "compact = \".a b\"").unwrap();
+        assert_eq!(
+            Config::from_file(&file).unwrap_err().to_string(),
+            format!(
+                "option `compact`: invalid Protobuf path `.a b`\n --> {}",
+                file.display()
+            )
+        );
+    }
+}
@@ -28,7 +28,6 @@ impl<T> PathMap<T> {
 
     /// Returns the first value found matching the given path
     /// If nothing matches the path, suffix paths will be tried, then prefix paths, then the global path
-    #[allow(unused)]
     pub(crate) fn get_first<'a>(&'a self, fq_path: &'_ str) -> Option<&'a T> {
         self.find_best_matching(fq_path)
     }
@@ -39,6 +38,11 @@ impl<T> PathMap<T> {
         self.find_best_matching(&format!("{}.{}", fq_path, field))
     }
 
+    /// Returns true if the path map has no matchers.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.matchers.is_empty()
+    }
+
     /// Removes all matchers from the path map.
     pub(crate) fn clear(&mut self) {
         self.matchers.clear();
@@ -0,0 +1,146 @@
+//! Code generation as a `protoc` plugin.
+
+use std::io::Result;
+use std::path::PathBuf;
+
+use prost::Message;
+use prost_types::compiler::{CodeGeneratorRequest, CodeGeneratorResponse, code_generator_response};
+
+use crate::Config;
+use crate::features::DescriptorFeatures;
+
+/// The `CodeGeneratorResponse.Feature` flags of the plugin: proto3 `optional` fields and
+/// Protobuf Editions are supported.
+const SUPPORTED_FEATURES: u64 = 0x1 | 0x2;
+/// The range of supported Protobuf Editions, as `google.protobuf.Edition` values.
+const MINIMUM_EDITION: i32 = 1000;
+const MAXIMUM_EDITION: i32 = 1001;
+
+impl Config {
+    /// Generates code as a `protoc` plugin, from an encoded `CodeGeneratorRequest` to an encoded
+    /// `CodeGeneratorResponse`.
+    ///
+    /// The request parameter, given to `protoc` with `--ppsc_opt`, is a comma-separated list of
+    /// `key=value` options applied with [`set_option`](Self::set_option). A comma within a value
+    /// is escaped as `\,`. The files named in the request are generated to the same file names
+    /// as [`compile_protos`](Self::compile_protos) writes, along with the include file when the
+    /// `include_file` option is set.
+    ///
+    /// As `protoc` expects, errors are reported in the response rather than returned.
+    ///
+    /// This is what the `protoc-gen-ppsc` binary runs:
+    ///
+    /// ```sh
+    /// protoc --ppsc_out=src/generated --ppsc_opt=compact=.,type_info items.proto
+    /// ```
+    pub fn plugin_response(&mut self, encoded_request: &[u8]) -> Vec<u8> {
+        let mut response = match self.plugin_files(encoded_request) {
+            Ok(file) => CodeGeneratorResponse {
+                file,
+                ..CodeGeneratorResponse::default()
+            },
+            Err(error) => CodeGeneratorResponse {
+                error: Some(error.to_string()),
+                ..CodeGeneratorResponse::default()
+            },
+        };
+        response.supported_features = Some(SUPPORTED_FEATURES);
+
+        let mut encoded = response.encode_to_vec();
+        // `prost-types` predates the `minimum_edition` and `maximum_edition` fields.
+        prost::encoding::int32::encode(3, &MINIMUM_EDITION, &mut encoded);
+        prost::encoding::int32::encode(4, &MAXIMUM_EDITION, &mut encoded);
+        encoded
+    }
+
+    fn plugin_files(
+        &mut self,
+        encoded_request: &[u8],
+    ) -> Result<Vec<code_generator_response::File>> {
+        let (request, features) = CodeGeneratorRequest::decode(encoded_request)
+            .and_then(|request| {
+                Ok((
+                    request,
+                    DescriptorFeatures::decode_plugin_request(encoded_request)?,
+                ))
+            })
+            .map_err(|error| crate::Error::Compile {
+                location: None,
+                message: format!("invalid CodeGeneratorRequest: {}", error),
+            })?;
+
+        for (key, value) in parse_parameter(request.parameter()) {
+            self.set_option(&key, &value)?;
+        }
+
+        // Only the requested files are generated, but their imports are needed to resolve the
+        // types they refer to.
+        let (files, imports): (Vec<_>, Vec<_>) = request.proto_file.into_iter().partition(|file| {
+            request
+                .file_to_generate
+                .iter()
+                .any(|name| name == file.name())
+        });
+        let files = self.generate_files(files, &imports, &features, Some(&PathBuf::new()))?;
+
+        Ok(files
+            .into_iter()
+            .map(|(name, content)| code_generator_response::File {
+                name: Some(name),
+                content: Some(content),
+                ..code_generator_response::File::default()
+            })
+            .collect())
+    }
+}
+
+/// Splits a plugin parameter into `key=value` options, on the commas not escaped as `\,`.
+/// Options without a value have an empty one.
+fn parse_parameter(parameter: &str) -> Vec<(String, String)> {
+    let mut options = Vec::new();
+    let mut option = String::new();
+    let mut chars = parameter.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&',') => {
+                option.push(',');
+                chars.next();
+            }
+            ',' => options.push(std::mem::take(&mut option)),
+            _ => option.push(c),
+        }
+    }
+    options.push(option);
+
+    options
+        .into_iter()
+        .filter(|option| !option.trim().is_empty())
+        .map(|option| match option.split_once('=') {
+            Some((key, value)) => (key.trim().to_owned(), value.to_owned()),
+            None => (option.trim().to_owned(), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_parameter() {
+        let option = |key: &str, value: &str| (key.to_owned(), value.to_owned());
+
+        assert!(parse_parameter("").is_empty());
+        assert_eq!(
+            parse_parameter("compact=.,type_info,"),
+            vec![option("compact", "."), option("type_info", "")]
+        );
+        assert_eq!(
+            parse_parameter(r"type_attribute=.a.B=#[derive(Eq\, Hash)],max_len=.a.B.c=16"),
+            vec![
+                option("type_attribute", ".a.B=#[derive(Eq, Hash)]"),
+                option("max_len", ".a.B.c=16"),
+            ]
+        );
+    }
+}